@@ -1,19 +1,28 @@
-use std::convert::TryInto;
+// the original bindings in this module predate these lints
+#![allow(clippy::needless_return)]
+
+use std::convert::TryFrom;
 use std::io::{stdout, Write};
-use std::time::Duration;
 
-use crossterm::{cursor, event, terminal};
+use crossterm::{cursor, terminal};
 use jni::{
     JNIEnv,
     objects::{JClass, JObject},
-    sys::{jboolean, jobject},
+    sys::{jboolean, jint, jintArray, jlong, jobject},
 };
 
 use crate::{
+    clipboard,
+    event_reader,
+    event_source::{self, TimedEvent},
+    half_blocks,
     jni_from_jvm,
     jni_to_jvm,
     jvm_unwrapper::JvmUnwrapper,
-    unify_errors::UnifyErrors,
+    sixel,
+    terminal_profile,
+    terminal_state,
+    unify_errors::{UnifiedError, UnifiedResult, UnifyErrors},
 };
 
 #[no_mangle]
@@ -59,13 +68,8 @@ pub extern "system" fn Java_tui_crossterm_CrosstermJni_poll(
     _class: JClass,
     timeout: JObject,
 ) -> jboolean {
-    let secs_jlong = env.get_field(timeout, "secs", "J").and_then(|x| x.j()).jvm_unwrap(env);
-    let nanos_jint = env.get_field(timeout, "nanos", "I").and_then(|x| x.i()).jvm_unwrap(env);
-    let duration = Duration::new(
-        secs_jlong.try_into().unwrap_or_default(),
-        nanos_jint.try_into().unwrap_or_default(),
-    );
-    let res = event::poll(duration).jvm_unwrap(env);
+    let duration = jni_from_jvm::duration(env, timeout).jvm_unwrap(env);
+    let res = event_source::poll(Some(duration)).jvm_unwrap(env);
     return res.into();
 }
 
@@ -74,10 +78,87 @@ pub extern "system" fn Java_tui_crossterm_CrosstermJni_read(
     env: JNIEnv,
     _class: JClass,
 ) -> jobject {
-    let e = event::read().unify_errors().and_then(|e| jni_to_jvm::event(env, e).unify_errors());
+    let e = event_source::read().unify_errors().and_then(|e| jni_to_jvm::source_event(env, e).unify_errors());
     return e.jvm_unwrap(env).into_raw();
 }
 
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_now(_env: JNIEnv, _class: JClass) -> jlong {
+    event_source::now()
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_setCoalescing(_env: JNIEnv, _class: JClass, enabled: jboolean) {
+    event_source::set_coalescing(enabled != 0)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_wake(env: JNIEnv, _class: JClass) {
+    event_source::wake().jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_registerFd(env: JNIEnv, _class: JClass, fd: jint) {
+    let registered = if fd < 0 {
+        Err(UnifiedError::InvalidArgument(format!("{fd} is not a file descriptor")))
+    } else {
+        event_source::register(fd).unify_errors()
+    };
+    registered.jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_unregisterFd(env: JNIEnv, _class: JClass, fd: jint) {
+    event_source::unregister(fd).jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_startTimer(env: JNIEnv, _class: JClass, delay: JObject) -> jlong {
+    let id = jni_from_jvm::duration(env, delay).unify_errors().and_then(|delay| event_source::start_timer(delay, None).unify_errors());
+    id.jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_startRepeatingTimer(
+    env: JNIEnv,
+    _class: JClass,
+    period: JObject,
+) -> jlong {
+    let id = jni_from_jvm::duration(env, period).unify_errors().and_then(|period| {
+        if period.is_zero() {
+            return Err(UnifiedError::InvalidArgument("the period of a timer must be positive".to_string()));
+        }
+        event_source::start_timer(period, Some(period)).unify_errors()
+    });
+    id.jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_cancelTimer(env: JNIEnv, _class: JClass, id: jlong) -> jboolean {
+    let cancelled = event_source::cancel_timer(id).jvm_unwrap(env);
+    cancelled.into()
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_readAll(
+    env: JNIEnv,
+    _class: JClass,
+    max_events: jint,
+    timeout: JObject,
+) -> jobject {
+    let events = read_all(env, max_events, timeout).and_then(|events| jni_to_jvm::event_list(env, events).unify_errors());
+    events.jvm_unwrap(env).into_raw()
+}
+
+/// See [`event_source::read_batch`].
+fn read_all(env: JNIEnv, max_events: jint, timeout: JObject) -> UnifiedResult<Vec<TimedEvent>> {
+    if max_events <= 0 {
+        return Err(UnifiedError::InvalidArgument(format!("maxEvents must be positive, was {max_events}")));
+    }
+    let timeout = jni_from_jvm::duration(env, timeout).unify_errors()?;
+    event_source::read_batch(max_events as usize, timeout).unify_errors()
+}
+
 #[no_mangle]
 pub extern "system" fn Java_tui_crossterm_CrosstermJni_enqueue(
     env: JNIEnv,
@@ -100,3 +181,99 @@ pub extern "system" fn Java_tui_crossterm_CrosstermJni_execute(
     jni_from_jvm::queue_commands(writer, env, commands_list_object).jvm_unwrap(env);
     writer.flush().jvm_unwrap(env);
 }
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_readClipboard0(
+    env: JNIEnv,
+    _class: JClass,
+    selection: JObject,
+    timeout: JObject,
+) -> jobject {
+    let selection = jni_from_jvm::clipboard_selection(env, selection).jvm_unwrap(env);
+    let timeout = jni_from_jvm::duration(env, timeout).jvm_unwrap(env);
+    let result = clipboard::read(selection, timeout)
+        .unify_errors()
+        .and_then(|text| match text {
+            Some(text) => env.new_string(text).map(JObject::from).unify_errors(),
+            None => Ok(JObject::null()),
+        });
+    result.jvm_unwrap(env).into_raw()
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_restoreTerminalState(env: JNIEnv, _class: JClass) {
+    let mut stdout1 = stdout();
+    let writer = stdout1.by_ref();
+    terminal_state::restore(writer).jvm_unwrap(env);
+    writer.flush().jvm_unwrap(env);
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_recoverTerminal(env: JNIEnv, _class: JClass) {
+    let mut stdout1 = stdout();
+    terminal_state::recover(stdout1.by_ref()).jvm_unwrap(env);
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_supportsInlineImages(_env: JNIEnv, _class: JClass) -> jboolean {
+    terminal_profile::detect().supports_inline_images().into()
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_cellSize(env: JNIEnv, _class: JClass) -> jobject {
+    let (width, height) = sixel::cell_size();
+    let xy = jni_to_jvm::xy(env, width, height).unify_errors();
+    return xy.jvm_unwrap(env).into_raw();
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_decodeImage(env: JNIEnv, _class: JClass, data: JObject) -> jobject {
+    let result = env
+        .convert_byte_array(data.into_raw())
+        .unify_errors()
+        .and_then(|data| half_blocks::decode(&data).map_err(UnifiedError::InvalidArgument))
+        .and_then(|image| {
+            if image.width() > u32::from(u16::MAX) || image.height() > u32::from(u16::MAX) {
+                return Err(UnifiedError::InvalidArgument(format!("image is too large: {}x{}", image.width(), image.height())));
+            }
+            jni_to_jvm::rgba_image(env, &image).unify_errors()
+        });
+    result.jvm_unwrap(env).into_raw()
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_halfBlocks(
+    env: JNIEnv,
+    _class: JClass,
+    image: JObject,
+    columns: jint,
+    rows: jint,
+) -> jintArray {
+    half_blocks_array(env, image, columns, rows).jvm_unwrap(env).into_raw()
+}
+
+fn half_blocks_array<'a>(env: JNIEnv<'a>, image: JObject<'a>, columns: jint, rows: jint) -> UnifiedResult<JObject<'a>> {
+    let (width, height, pixels) = jni_from_jvm::rgba_image(env, image)?;
+    let (columns, rows) = match (u16::try_from(columns), u16::try_from(rows)) {
+        (Ok(columns), Ok(rows)) if columns > 0 && rows > 0 => (columns, rows),
+        _ => return Err(UnifiedError::InvalidArgument(format!("target must not be empty, was {columns}x{rows} cells"))),
+    };
+    // the size was checked against the pixels by `rgba_image`
+    let image = image::RgbaImage::from_raw(width.into(), height.into(), pixels).unwrap();
+    let colors = half_blocks::half_blocks(&image, columns, rows);
+    let array = env.new_int_array(colors.len() as jint).unify_errors()?;
+    env.set_int_array_region(array, 0, &colors).unify_errors()?;
+    // a local reference which was just created, so it is valid for the lifetime of `env`
+    Ok(unsafe { JObject::from_raw(array) })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_startEventReader(env: JNIEnv, _class: JClass, consumer: JObject) {
+    let started = env.new_global_ref(consumer).unify_errors().and_then(|consumer| event_reader::start(env, consumer));
+    started.jvm_unwrap(env)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_tui_crossterm_CrosstermJni_stopEventReader(env: JNIEnv, _class: JClass) {
+    event_reader::stop(env).jvm_unwrap(env)
+}
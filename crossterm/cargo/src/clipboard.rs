@@ -0,0 +1,48 @@
+//! Reading and writing the clipboard through OSC 52, which also works over SSH.
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crossterm::Command;
+
+use crate::{osc, tty_query};
+
+/// Sets the contents of a clipboard selection (`c` for the clipboard, `p` for the primary
+/// selection, `q` for the secondary selection).
+pub struct SetClipboard {
+    pub selection: char,
+    pub text: String,
+}
+
+impl Command for SetClipboard {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, &format!("52;{};{}", self.selection, STANDARD.encode(&self.text)))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "OSC 52 is not implemented for the legacy Windows API."))
+    }
+}
+
+/// Asks the terminal for the contents of a clipboard selection. Returns `None` if the terminal
+/// did not answer within `timeout`, which is what happens when reading is disabled or unsupported.
+pub fn read(selection: char, timeout: Duration) -> io::Result<Option<String>> {
+    let find_reply = |input: &[u8]| tty_query::osc_reply_range(input, b"52;");
+    let reply = match tty_query::query(&osc::osc(&format!("52;{};?", selection)), timeout, find_reply)? {
+        Some(reply) => reply,
+        None => return Ok(None),
+    };
+    // the payload is `selection;base64`, where the selection may be left out
+    let payload = tty_query::osc_reply(&reply, b"52;").unwrap_or_default();
+    let data = match payload.iter().position(|&b| b == b';') {
+        Some(i) => &payload[i + 1..],
+        None => payload,
+    };
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("invalid clipboard reply: {err}")))?;
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
@@ -0,0 +1,380 @@
+//! Commands for escape sequences which crossterm itself does not provide.
+//!
+//! These implement `crossterm::Command` so they can be queued exactly like the built-in ones,
+//! instead of pushing raw escape sequences through `Print`.
+
+use std::fmt;
+
+use crossterm::Command;
+
+use crate::{osc, terminal_profile};
+
+/// The legacy Windows console API has no equivalent for any of the sequences written by the
+/// commands in this crate.
+#[cfg(windows)]
+pub(crate) fn unsupported_winapi(name: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{name} is not implemented for the legacy Windows API."),
+    ))
+}
+
+/// Sets the scrolling region (DECSTBM) to the rows `top..=bottom` (0 based).
+pub struct SetScrollRegion(pub u16, pub u16);
+
+impl Command for SetScrollRegion {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b[{};{}r", u32::from(self.0) + 1, u32::from(self.1) + 1)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("SetScrollRegion")
+    }
+}
+
+/// Resets the scrolling region to the full screen.
+pub struct ResetScrollRegion;
+
+impl Command for ResetScrollRegion {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[r")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("ResetScrollRegion")
+    }
+}
+
+/// Scrolls the rows `first_row..=last_row` (0 based) up or down, leaving the rest of the screen
+/// alone. The scrolling region is reset afterwards, and the cursor position is undefined.
+pub struct ScrollInRegion {
+    pub first_row: u16,
+    pub last_row: u16,
+    pub lines_to_scroll: u16,
+    pub up: bool,
+}
+
+impl Command for ScrollInRegion {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.lines_to_scroll == 0 {
+            return Ok(());
+        }
+        if self.first_row == self.last_row {
+            // DECSTBM needs at least two rows, and terminals ignore it otherwise, which would
+            // scroll the whole screen. Scrolling a single row just blanks it.
+            return write!(f, "\x1b[{};1H\x1b[2K", u32::from(self.first_row) + 1);
+        }
+        SetScrollRegion(self.first_row, self.last_row).write_ansi(f)?;
+        write!(f, "\x1b[{}{}", self.lines_to_scroll, if self.up { 'S' } else { 'T' })?;
+        ResetScrollRegion.write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("ScrollInRegion")
+    }
+}
+
+/// Declares a command that takes a single count, written as `CSI count final_byte`. Nothing is
+/// written for a count of 0, which terminals would take as 1.
+macro_rules! csi_count_command {
+    ($(#[$meta:meta])* $name:ident, $final_byte:literal) => {
+        $(#[$meta])*
+        pub struct $name(pub u16);
+
+        impl Command for $name {
+            fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+                if self.0 == 0 {
+                    return Ok(());
+                }
+                write!(f, "\x1b[{}{}", self.0, $final_byte)
+            }
+
+            #[cfg(windows)]
+            fn execute_winapi(&self) -> std::io::Result<()> {
+                unsupported_winapi(stringify!($name))
+            }
+        }
+    };
+}
+
+csi_count_command!(
+    /// Inserts blank lines at the cursor row (IL), pushing the rows below down.
+    InsertLines, 'L'
+);
+csi_count_command!(
+    /// Deletes lines starting at the cursor row (DL), pulling the rows below up.
+    DeleteLines, 'M'
+);
+csi_count_command!(
+    /// Inserts blank characters at the cursor (ICH), shifting the rest of the row right.
+    InsertChars, '@'
+);
+csi_count_command!(
+    /// Deletes characters at the cursor (DCH), shifting the rest of the row left.
+    DeleteChars, 'P'
+);
+csi_count_command!(
+    /// Erases characters from the cursor onwards (ECH) without shifting anything.
+    EraseChars, 'X'
+);
+
+pub enum ProgressState {
+    Normal,
+    Error,
+    Indeterminate,
+    Paused,
+}
+
+/// Reports progress in the tab or taskbar with `OSC 9 ; 4 ; state ; percent`. Writes nothing on
+/// terminals which are not known to support it.
+pub struct SetProgress(pub ProgressState, pub u8);
+
+impl Command for SetProgress {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if !terminal_profile::detect().supports_progress() {
+            return Ok(());
+        }
+        let state = match self.0 {
+            ProgressState::Normal => 1,
+            ProgressState::Error => 2,
+            ProgressState::Indeterminate => 3,
+            ProgressState::Paused => 4,
+        };
+        osc::write_osc(f, &format!("9;4;{};{}", state, self.1))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("SetProgress")
+    }
+}
+
+/// Removes the progress indicator set by [`SetProgress`].
+pub struct ClearProgress;
+
+impl Command for ClearProgress {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if !terminal_profile::detect().supports_progress() {
+            return Ok(());
+        }
+        osc::write_osc(f, "9;4;0;0")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("ClearProgress")
+    }
+}
+
+/// The CSS cursor names accepted by [`SetPointerShape`].
+pub const POINTER_SHAPES: &[&str] = &[
+    "default", "none", "context-menu", "help", "pointer", "progress", "wait", "cell", "crosshair",
+    "text", "vertical-text", "alias", "copy", "move", "no-drop", "not-allowed", "grab", "grabbing",
+    "all-scroll", "col-resize", "row-resize", "n-resize", "e-resize", "s-resize", "w-resize",
+    "ne-resize", "nw-resize", "se-resize", "sw-resize", "ew-resize", "ns-resize", "nesw-resize",
+    "nwse-resize", "zoom-in", "zoom-out",
+];
+
+/// Changes the shape of the mouse pointer while it is over the terminal (`OSC 22`). Pushing
+/// keeps the current shape on the terminal's stack of pointer shapes, so [`PopPointerShape`]
+/// can put it back, otherwise the shape on top of the stack is replaced.
+pub struct SetPointerShape {
+    pub name: String,
+    pub push: bool,
+}
+
+impl Command for SetPointerShape {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.push {
+            osc::write_osc(f, &format!("22;>{}", self.name))
+        } else {
+            osc::write_osc(f, &format!("22;{}", self.name))
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("SetPointerShape")
+    }
+}
+
+/// Changes the mouse pointer back to the shape it had before the last pushed [`SetPointerShape`].
+pub struct PopPointerShape;
+
+impl Command for PopPointerShape {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, "22;<")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("PopPointerShape")
+    }
+}
+
+/// Window manipulation (XTWINOPS, `CSI Ps t`). Many terminals ignore some or all of these, or
+/// only honour them when configured to.
+pub enum WindowOp {
+    Deiconify,
+    Iconify,
+    /// Moves the top left corner of the window to the given pixel position on the screen.
+    Move { x: u16, y: u16 },
+    /// Resizes the text area to the given size in pixels. 0 keeps the current size.
+    ResizePixels { width: u16, height: u16 },
+    Raise,
+    Lower,
+    Maximize,
+    Unmaximize,
+    EnterFullscreen,
+    ExitFullscreen,
+    ToggleFullscreen,
+}
+
+impl Command for WindowOp {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            WindowOp::Deiconify => f.write_str("\x1b[1t"),
+            WindowOp::Iconify => f.write_str("\x1b[2t"),
+            WindowOp::Move { x, y } => write!(f, "\x1b[3;{x};{y}t"),
+            WindowOp::ResizePixels { width, height } => write!(f, "\x1b[4;{height};{width}t"),
+            WindowOp::Raise => f.write_str("\x1b[5t"),
+            WindowOp::Lower => f.write_str("\x1b[6t"),
+            WindowOp::Unmaximize => f.write_str("\x1b[9;0t"),
+            WindowOp::Maximize => f.write_str("\x1b[9;1t"),
+            WindowOp::ExitFullscreen => f.write_str("\x1b[10;0t"),
+            WindowOp::EnterFullscreen => f.write_str("\x1b[10;1t"),
+            WindowOp::ToggleFullscreen => f.write_str("\x1b[10;2t"),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("Window manipulation")
+    }
+}
+
+/// Soft terminal reset (DECSTR). Resets modes like the scrolling region, character sets, SGR
+/// attributes and cursor visibility, but leaves the screen contents alone.
+pub struct SoftReset;
+
+impl Command for SoftReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[!p")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("SoftReset")
+    }
+}
+
+/// Full terminal reset (RIS). Resets everything and clears the screen, like the `reset` command.
+pub struct FullReset;
+
+impl Command for FullReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1bc")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("FullReset")
+    }
+}
+
+/// Pops several levels of keyboard enhancement flags at once.
+pub struct PopKeyboardEnhancementFlagsN(pub u16);
+
+impl Command for PopKeyboardEnhancementFlagsN {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b[<{}u", self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("PopKeyboardEnhancementFlags")
+    }
+}
+
+/// Makes the terminal turn mouse wheel scrolls into Up/Down arrow keys on the alternate screen,
+/// while mouse capture is disabled (DEC mode 1007).
+pub struct EnableAlternateScroll;
+
+impl Command for EnableAlternateScroll {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[?1007h")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("EnableAlternateScroll")
+    }
+}
+
+/// Stops turning mouse wheel scrolls into arrow keys, see [`EnableAlternateScroll`].
+pub struct DisableAlternateScroll;
+
+impl Command for DisableAlternateScroll {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[?1007l")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        unsupported_winapi("DisableAlternateScroll")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ansi(command: impl Command) -> String {
+        let mut s = String::new();
+        command.write_ansi(&mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn line_and_char_counts() {
+        assert_eq!(ansi(InsertLines(3)), "\x1b[3L");
+        assert_eq!(ansi(DeleteLines(3)), "\x1b[3M");
+        assert_eq!(ansi(InsertChars(2)), "\x1b[2@");
+        assert_eq!(ansi(DeleteChars(2)), "\x1b[2P");
+        assert_eq!(ansi(EraseChars(65535)), "\x1b[65535X");
+    }
+
+    #[test]
+    fn zero_counts_write_nothing() {
+        // terminals take a count of 0 as 1
+        assert_eq!(ansi(InsertLines(0)), "");
+        assert_eq!(ansi(DeleteLines(0)), "");
+        assert_eq!(ansi(InsertChars(0)), "");
+        assert_eq!(ansi(DeleteChars(0)), "");
+        assert_eq!(ansi(EraseChars(0)), "");
+    }
+
+    #[test]
+    fn window_ops() {
+        assert_eq!(ansi(WindowOp::Deiconify), "\x1b[1t");
+        assert_eq!(ansi(WindowOp::Iconify), "\x1b[2t");
+        assert_eq!(ansi(WindowOp::Raise), "\x1b[5t");
+        assert_eq!(ansi(WindowOp::Lower), "\x1b[6t");
+        assert_eq!(ansi(WindowOp::Unmaximize), "\x1b[9;0t");
+        assert_eq!(ansi(WindowOp::Maximize), "\x1b[9;1t");
+        assert_eq!(ansi(WindowOp::ExitFullscreen), "\x1b[10;0t");
+        assert_eq!(ansi(WindowOp::EnterFullscreen), "\x1b[10;1t");
+        assert_eq!(ansi(WindowOp::ToggleFullscreen), "\x1b[10;2t");
+    }
+
+    #[test]
+    fn window_ops_with_coordinates() {
+        // moving takes x before y, but resizing takes the height before the width
+        assert_eq!(ansi(WindowOp::Move { x: 10, y: 20 }), "\x1b[3;10;20t");
+        assert_eq!(ansi(WindowOp::ResizePixels { width: 640, height: 480 }), "\x1b[4;480;640t");
+        assert_eq!(ansi(WindowOp::ResizePixels { width: 0, height: 480 }), "\x1b[4;480;0t");
+    }
+}
@@ -0,0 +1,131 @@
+//! A native thread which reads events and hands them to a Java `Consumer<Event>`, so Java does
+//! not need a thread of its own looping on `poll` and `read`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use jni::errors::Result as JniResult;
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::{JNIEnv, JavaVM};
+
+use crate::event_source::{self, TimedEvent};
+use crate::jni_to_jvm;
+use crate::unify_errors::{UnifiedError, UnifiedResult, UnifyErrors};
+
+/// What the reader thread shares with whoever stops it.
+#[derive(Default)]
+struct Shared {
+    stop: AtomicBool,
+    /// The `java.lang.Thread` of the reader, once it is attached to the JVM.
+    java_thread: OnceLock<GlobalRef>,
+}
+
+struct Reader {
+    shared: Arc<Shared>,
+    handle: JoinHandle<()>,
+}
+
+static READER: Mutex<Option<Reader>> = Mutex::new(None);
+
+/// Starts the reader thread, which calls `consumer.accept(event)` for every event until
+/// [`stop`] is called, or until reading fails, which is reported with a final
+/// `Event.ReaderFailed`.
+pub fn start(env: JNIEnv, consumer: GlobalRef) -> UnifiedResult<()> {
+    let mut reader = READER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if reader.as_ref().is_some_and(|r| !r.handle.is_finished()) {
+        return Err(UnifiedError::IllegalState("the event reader is already running".to_string()));
+    }
+    let vm = env.get_java_vm().unify_errors()?;
+    let shared = Arc::new(Shared::default());
+    let handle = thread::Builder::new()
+        .name("crossterm-event-reader".to_string())
+        .spawn({
+            let shared = shared.clone();
+            move || run(vm, consumer, shared)
+        })
+        .unify_errors()?;
+    *reader = Some(Reader { shared, handle });
+    Ok(())
+}
+
+/// Stops the reader thread and waits for it to finish, unless called from the consumer itself.
+/// Does nothing if the reader is not running.
+///
+/// A reader waiting for events is woken up, and a consumer blocked in Java, for instance on a
+/// full queue, is interrupted, so the thread can always finish.
+pub fn stop(env: JNIEnv) -> UnifiedResult<()> {
+    let reader = READER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+    let reader = match reader {
+        Some(reader) => reader,
+        None => return Ok(()),
+    };
+    reader.shared.stop.store(true, Ordering::SeqCst);
+    // the consumer may stop the reader, but a thread can not wait for itself
+    if reader.handle.thread().id() == thread::current().id() {
+        return Ok(());
+    }
+    event_source::interrupt().unify_errors()?;
+    if let Some(java_thread) = reader.shared.java_thread.get() {
+        env.call_method(java_thread.as_obj(), "interrupt", "()V", &[]).unify_errors()?;
+    }
+    let _ = reader.handle.join();
+    Ok(())
+}
+
+fn run(vm: JavaVM, consumer: GlobalRef, shared: Arc<Shared>) {
+    // attached once for the lifetime of the thread, and detached when the guard is dropped
+    let env = match vm.attach_current_thread() {
+        Ok(env) => env,
+        Err(_) => return,
+    };
+    // without the Java thread a blocked consumer can not be interrupted, but events still flow
+    match current_thread(*env) {
+        Ok(java_thread) => {
+            let _ = shared.java_thread.set(java_thread);
+        }
+        Err(_) => {
+            if env.exception_check().unwrap_or(false) {
+                let _ = env.exception_describe();
+                let _ = env.exception_clear();
+            }
+        }
+    }
+    loop {
+        match next(&shared.stop) {
+            Ok(Some(e)) => deliver(*env, &consumer, |env| jni_to_jvm::source_event(env, e)),
+            Ok(None) => return,
+            Err(err) => {
+                let timestamp = event_source::now();
+                deliver(*env, &consumer, |env| jni_to_jvm::reader_failed(env, &err.to_string(), timestamp));
+                return;
+            }
+        }
+    }
+}
+
+fn current_thread(env: JNIEnv) -> JniResult<GlobalRef> {
+    let thread = env.call_static_method("java/lang/Thread", "currentThread", "()Ljava/lang/Thread;", &[])?.l()?;
+    env.new_global_ref(thread)
+}
+
+/// Waits for the next event, or returns `None` once the reader should stop.
+fn next(stop: &AtomicBool) -> std::io::Result<Option<TimedEvent>> {
+    if !event_source::poll_while(None, || !stop.load(Ordering::SeqCst))? {
+        return Ok(None);
+    }
+    event_source::read().map(Some)
+}
+
+fn deliver<'a>(env: JNIEnv<'a>, consumer: &GlobalRef, event: impl FnOnce(JNIEnv<'a>) -> JniResult<JObject<'a>>) {
+    let delivered = env.with_local_frame(16, || {
+        let obj = event(env)?;
+        env.call_method(consumer.as_obj(), "accept", "(Ljava/lang/Object;)V", &[JValue::Object(obj)])?;
+        Ok(JObject::null())
+    });
+    // an exception thrown by the consumer must not end the thread, nor be left pending
+    if delivered.is_err() && env.exception_check().unwrap_or(false) {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+    }
+}
@@ -0,0 +1,501 @@
+//! Waiting for terminal events together with wake ups from other threads, timers, and readable
+//! file descriptors registered by the application.
+//!
+//! crossterm's own `poll` can not be interrupted, so on unix this waits with `poll(2)` on the
+//! terminal, a wake up pipe, a pipe written on `SIGWINCH` and the registered file descriptors, and
+//! only asks crossterm for events once the terminal may have some.
+//!
+//! Every event carries a monotonic timestamp from [`now`]. Terminal input is only parsed while
+//! some thread is in [`poll`] or [`read`], and each event is stamped when it is parsed and
+//! queued here. While a thread keeps waiting, like the event reader does, that is when the
+//! input arrives. Otherwise it is when the application next polled, so input which arrived in
+//! between gets the time of that poll, not of its arrival, while events which are already queued
+//! keep their earlier timestamp however late they are read.
+//!
+//! With [`set_coalescing`], mouse moves, drags and resizes which are superseded by the next
+//! queued event are merged into it, so a fast drag does not flood the application.
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crossterm::event;
+
+use crate::{pixel_mouse, timers};
+
+pub use sys::{register, unregister};
+
+/// An event from the terminal, a wake up requested through [`wake`], a timer started with
+/// [`start_timer`] which is due, or registered file descriptors which are ready to be read.
+pub enum SourceEvent {
+    /// `merged` counts the earlier events which were coalesced into this one, and `pixel` holds
+    /// the pixel position of a mouse event while pixel mouse reporting is enabled.
+    Terminal { event: event::Event, merged: u32, pixel: Option<(u16, u16)> },
+    Woken,
+    Timer(i64),
+    Readable(Vec<i32>),
+}
+
+/// An event together with the [`now`] at which it was parsed or noticed.
+pub struct TimedEvent {
+    pub event: SourceEvent,
+    pub timestamp: i64,
+}
+
+/// A terminal event which crossterm has parsed but which has not been read yet.
+struct Parsed {
+    event: event::Event,
+    merged: u32,
+    pixel: Option<(u16, u16)>,
+    timestamp: i64,
+}
+
+static PARSED: Mutex<VecDeque<Parsed>> = Mutex::new(VecDeque::new());
+
+static COALESCING: AtomicBool = AtomicBool::new(false);
+
+/// How many parsed events are queued at most, so a flood of input can not keep us parsing.
+const MAX_PARSED: usize = 1024;
+
+/// Nanoseconds on a monotonic clock, counted from the first time it was read in this process.
+pub fn now() -> i64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    let epoch = *EPOCH.get_or_init(Instant::now);
+    Instant::now().duration_since(epoch).as_nanos() as i64
+}
+
+/// Turns coalescing of consecutive mouse moves, drags with the same button and modifiers, and
+/// resizes on or off. Only events which are queued and not read yet are merged.
+pub fn set_coalescing(enabled: bool) {
+    COALESCING.store(enabled, Ordering::SeqCst);
+}
+
+/// Whether `next` supersedes `previous`, so only the latest of them needs to be delivered.
+fn supersedes(previous: &event::Event, next: &event::Event) -> bool {
+    use event::{Event, MouseEventKind};
+    match (previous, next) {
+        (Event::Resize(..), Event::Resize(..)) => true,
+        (Event::Mouse(previous), Event::Mouse(next)) => {
+            let motion = matches!(next.kind, MouseEventKind::Moved | MouseEventKind::Drag(_));
+            // the same kind also means the same button for drags
+            motion && previous.kind == next.kind && previous.modifiers == next.modifiers
+        }
+        _ => false,
+    }
+}
+
+/// Set by [`wake`] and cleared when [`read`] returns [`SourceEvent::Woken`], so any number of
+/// wake ups before the next read are delivered once.
+static WOKEN: AtomicBool = AtomicBool::new(false);
+
+/// Makes a blocked or later [`poll`] return `true`, and the next [`read`] return
+/// [`SourceEvent::Woken`]. May be called from any thread.
+pub fn wake() -> io::Result<()> {
+    WOKEN.store(true, Ordering::SeqCst);
+    sys::notify()
+}
+
+/// Makes threads blocked in [`poll_while`] check whether they should keep waiting. Unlike
+/// [`wake`], no event is delivered.
+pub fn interrupt() -> io::Result<()> {
+    sys::notify()
+}
+
+/// Starts a timer which is delivered as [`SourceEvent::Timer`] after `delay`, and then every
+/// `period` if there is one. Returns its id.
+pub fn start_timer(delay: Duration, period: Option<Duration>) -> io::Result<i64> {
+    let id = timers::start(delay, period);
+    // a thread which is already waiting picks up the earlier deadline
+    sys::notify()?;
+    Ok(id)
+}
+
+/// Cancels a timer started with [`start_timer`]. Returns whether it was still active.
+pub fn cancel_timer(id: i64) -> io::Result<bool> {
+    let cancelled = timers::cancel(id);
+    sys::notify()?;
+    Ok(cancelled)
+}
+
+/// Waits until an event is available or `timeout` has passed. `None` waits forever.
+pub fn poll(timeout: Option<Duration>) -> io::Result<bool> {
+    poll_while(timeout, || true)
+}
+
+/// Like [`poll`], but gives up and returns `false` once `keep_waiting` returns `false`, which is
+/// checked before waiting and whenever the wait is interrupted with [`interrupt`].
+pub fn poll_while(timeout: Option<Duration>, keep_waiting: impl Fn() -> bool) -> io::Result<bool> {
+    // a timeout too large for an `Instant` is as good as none
+    let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    loop {
+        if !keep_waiting() {
+            return Ok(false);
+        }
+        if pending()? {
+            return Ok(true);
+        }
+        let now = Instant::now();
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(now));
+        let until_timer = timers::next_due().map(|due| due.saturating_duration_since(now));
+        let wait = match (remaining, until_timer) {
+            (Some(remaining), Some(until_timer)) => Some(remaining.min(until_timer)),
+            (remaining, until_timer) => remaining.or(until_timer),
+        };
+        if !sys::wait(wait)?.is_empty() {
+            return Ok(true);
+        }
+        if remaining == Some(Duration::ZERO) {
+            return pending();
+        }
+    }
+}
+
+/// Whether a wake up, a due timer or a terminal event is available without waiting.
+fn pending() -> io::Result<bool> {
+    let timer_due = timers::next_due().is_some_and(|due| due <= Instant::now());
+    Ok(WOKEN.load(Ordering::SeqCst) || timer_due || parse()?)
+}
+
+/// Moves every event crossterm can parse from the input so far to [`PARSED`], stamped with the
+/// current time. Returns whether any parsed events are queued.
+fn parse() -> io::Result<bool> {
+    let mut parsed = PARSED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    parse_into(&mut parsed)?;
+    Ok(!parsed.is_empty())
+}
+
+fn parse_into(parsed: &mut VecDeque<Parsed>) -> io::Result<()> {
+    while parsed.len() < MAX_PARSED && event::poll(Duration::ZERO)? {
+        let event = event::read()?;
+        push(parsed, event, now());
+    }
+    Ok(())
+}
+
+/// Queues an event, merging it into the last queued one if coalescing is on and it supersedes it.
+fn push(parsed: &mut VecDeque<Parsed>, mut event: event::Event, timestamp: i64) {
+    let pixel = match &mut event {
+        event::Event::Mouse(e) => pixel_mouse::translate(e),
+        _ => None,
+    };
+    queue(parsed, Parsed { event, merged: 0, pixel, timestamp }, COALESCING.load(Ordering::SeqCst));
+}
+
+fn queue(parsed: &mut VecDeque<Parsed>, next: Parsed, coalescing: bool) {
+    match parsed.back_mut() {
+        Some(last) if coalescing && supersedes(&last.event, &next.event) => {
+            *last = Parsed { merged: last.merged + 1, ..next };
+        }
+        _ => parsed.push_back(next),
+    }
+}
+
+/// Runs `f` while no other thread reads terminal input, so `f` can read the reply to a query
+/// directly from the terminal. Input which was already available is queued first, and the events
+/// `f` hands back, for keys typed while it waited for the reply, are queued after it.
+pub fn with_exclusive_input<T>(f: impl FnOnce() -> io::Result<(T, Vec<event::Event>)>) -> io::Result<T> {
+    let mut parsed = PARSED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    parse_into(&mut parsed)?;
+    let (result, events) = f()?;
+    let timestamp = now();
+    let handed_back = !events.is_empty();
+    for event in events {
+        push(&mut parsed, event, timestamp);
+    }
+    drop(parsed);
+    if handed_back {
+        // a thread which is already waiting would not notice the queued events otherwise
+        sys::notify()?;
+    }
+    Ok(result)
+}
+
+/// Blocks until an event is available and returns it. Wake ups come first, then terminal
+/// events and timers, so neither a fast timer nor a file descriptor which is never drained can
+/// starve the terminal.
+pub fn read() -> io::Result<TimedEvent> {
+    loop {
+        poll(None)?;
+        if WOKEN.swap(false, Ordering::SeqCst) {
+            return Ok(TimedEvent { event: SourceEvent::Woken, timestamp: now() });
+        }
+        if let Some(Parsed { event, merged, pixel, timestamp }) = PARSED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).pop_front() {
+            return Ok(TimedEvent { event: SourceEvent::Terminal { event, merged, pixel }, timestamp });
+        }
+        if let Some(id) = timers::take_due(Instant::now()) {
+            return Ok(TimedEvent { event: SourceEvent::Timer(id), timestamp: now() });
+        }
+        let ready = sys::wait(Some(Duration::ZERO))?;
+        if !ready.is_empty() {
+            return Ok(TimedEvent { event: SourceEvent::Readable(ready), timestamp: now() });
+        }
+    }
+}
+
+/// Waits up to `timeout` for the first event, then takes whatever else is available without
+/// waiting, up to `max` events in total. Stops after readable file descriptors, which stay
+/// readable until the application reads them, so looking again would only report them again.
+pub fn read_batch(max: usize, timeout: Duration) -> io::Result<Vec<TimedEvent>> {
+    let mut wait = timeout;
+    let mut events = Vec::new();
+    while events.len() < max && poll(Some(wait))? {
+        let event = read()?;
+        let readable = matches!(event.event, SourceEvent::Readable(_));
+        events.push(event);
+        if readable {
+            break;
+        }
+        wait = Duration::ZERO;
+    }
+    Ok(events)
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::File;
+    use std::io::{self, Read, Write};
+    use std::os::unix::io::{AsRawFd, RawFd};
+    use std::os::unix::net::UnixStream;
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Duration;
+
+    struct Pipes {
+        /// The terminal crossterm reads from: stdin if it is a terminal, `/dev/tty` otherwise.
+        tty: RawFd,
+        _tty_file: Option<File>,
+        wake_rx: UnixStream,
+        wake_tx: UnixStream,
+        resize_rx: UnixStream,
+    }
+
+    static PIPES: OnceLock<Result<Pipes, String>> = OnceLock::new();
+
+    /// File descriptors registered by the application, in the order they were registered.
+    static REGISTERED: Mutex<Vec<RawFd>> = Mutex::new(Vec::new());
+
+    fn pipes() -> io::Result<&'static Pipes> {
+        // opening fails the same way every time, so the error is kept rather than retried
+        let pipes = PIPES.get_or_init(|| open().map_err(|err| err.to_string()));
+        pipes.as_ref().map_err(|err| io::Error::other(err.clone()))
+    }
+
+    fn open() -> io::Result<Pipes> {
+        let (tty, tty_file) = if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+            (libc::STDIN_FILENO, None)
+        } else {
+            let file = File::open("/dev/tty")?;
+            (file.as_raw_fd(), Some(file))
+        };
+        let (wake_rx, wake_tx) = UnixStream::pair()?;
+        let (resize_rx, resize_tx) = UnixStream::pair()?;
+        for stream in [&wake_rx, &wake_tx, &resize_rx, &resize_tx] {
+            stream.set_nonblocking(true)?;
+        }
+        // crossterm notices resizes through its own signal pipe, which we do not see
+        signal_hook::low_level::pipe::register(libc::SIGWINCH, resize_tx)?;
+        Ok(Pipes { tty, _tty_file: tty_file, wake_rx, wake_tx, resize_rx })
+    }
+
+    /// Adds `fd` to the descriptors [`super::poll`] waits on. Registering it twice does nothing.
+    pub fn register(fd: RawFd) -> io::Result<()> {
+        if unsafe { libc::fcntl(fd, libc::F_GETFD) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut registered = REGISTERED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !registered.contains(&fd) {
+            registered.push(fd);
+        }
+        drop(registered);
+        // a thread which is already waiting picks up the change
+        notify()
+    }
+
+    /// Stops waiting on `fd`. Does nothing if it was not registered.
+    pub fn unregister(fd: RawFd) -> io::Result<()> {
+        REGISTERED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).retain(|&r| r != fd);
+        notify()
+    }
+
+    pub fn notify() -> io::Result<()> {
+        match (&pipes()?.wake_tx).write(&[1]) {
+            // a full pipe already wakes the poller
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// Waits until the terminal has input, a wake up or resize arrives, a registered file
+    /// descriptor is ready, or `timeout` passes. Returns the registered descriptors which are
+    /// ready, including those which were closed or hung up.
+    pub fn wait(timeout: Option<Duration>) -> io::Result<Vec<RawFd>> {
+        let pipes = pipes()?;
+        let registered = REGISTERED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        let mut fds: Vec<libc::pollfd> = [pipes.tty, pipes.wake_rx.as_raw_fd(), pipes.resize_rx.as_raw_fd()]
+            .iter()
+            .chain(&registered)
+            .map(|&fd| libc::pollfd { fd, events: libc::POLLIN, revents: 0 })
+            .collect();
+        // rounded up, so we do not wake up just before the deadline and spin
+        let millis = timeout.map_or(-1, |t| t.as_nanos().div_ceil(1_000_000).min(i32::MAX as u128) as i32);
+        if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, millis) } < 0 {
+            let err = io::Error::last_os_error();
+            return if err.kind() == io::ErrorKind::Interrupted { Ok(Vec::new()) } else { Err(err) };
+        }
+        drain(&pipes.wake_rx);
+        drain(&pipes.resize_rx);
+        Ok(fds[3..].iter().filter(|fd| fd.revents != 0).map(|fd| fd.fd).collect())
+    }
+
+    fn drain(mut stream: &UnixStream) {
+        let mut buf = [0u8; 64];
+        while matches!(stream.read(&mut buf), Ok(n) if n > 0) {}
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::io;
+    use std::time::Duration;
+
+    use crossterm::event;
+
+    /// How long each wait lasts before checking for a wake up again.
+    const SLICE: Duration = Duration::from_millis(10);
+
+    pub fn register(_fd: i32) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Waiting on file descriptors is not implemented on Windows."))
+    }
+
+    pub fn unregister(_fd: i32) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn notify() -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn wait(timeout: Option<Duration>) -> io::Result<Vec<i32>> {
+        event::poll(timeout.map_or(SLICE, |t| t.min(SLICE))).map(|_| Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    fn parsed(event: Event, timestamp: i64) -> Parsed {
+        Parsed { event, merged: 0, pixel: None, timestamp }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, modifiers: KeyModifiers) -> Event {
+        Event::Mouse(MouseEvent { kind, column, row: 0, modifiers })
+    }
+
+    #[test]
+    fn moves_drags_and_resizes_supersede_their_kind() {
+        let none = KeyModifiers::NONE;
+        assert!(supersedes(&Event::Resize(80, 24), &Event::Resize(100, 30)));
+        assert!(supersedes(&mouse(MouseEventKind::Moved, 1, none), &mouse(MouseEventKind::Moved, 2, none)));
+        let drag = MouseEventKind::Drag(MouseButton::Left);
+        assert!(supersedes(&mouse(drag, 1, none), &mouse(drag, 2, none)));
+    }
+
+    #[test]
+    fn other_events_do_not_supersede() {
+        let none = KeyModifiers::NONE;
+        let left = MouseEventKind::Drag(MouseButton::Left);
+        let right = MouseEventKind::Drag(MouseButton::Right);
+        assert!(!supersedes(&mouse(left, 1, none), &mouse(right, 2, none)));
+        assert!(!supersedes(&mouse(left, 1, none), &mouse(left, 2, KeyModifiers::SHIFT)));
+        assert!(!supersedes(&mouse(MouseEventKind::Moved, 1, none), &mouse(left, 2, none)));
+        let down = MouseEventKind::Down(MouseButton::Left);
+        assert!(!supersedes(&mouse(down, 1, none), &mouse(down, 2, none)));
+        assert!(!supersedes(&mouse(MouseEventKind::ScrollUp, 1, none), &mouse(MouseEventKind::ScrollUp, 2, none)));
+        assert!(!supersedes(&key('a'), &key('a')));
+        assert!(!supersedes(&Event::Resize(80, 24), &mouse(MouseEventKind::Moved, 1, none)));
+    }
+
+    #[test]
+    fn coalescing_merges_into_the_last_event() {
+        let none = KeyModifiers::NONE;
+        let mut queued = VecDeque::new();
+        for (column, timestamp) in [(1, 10), (2, 20), (3, 30)] {
+            queue(&mut queued, parsed(mouse(MouseEventKind::Moved, column, none), timestamp), true);
+        }
+        queue(&mut queued, parsed(key('a'), 40), true);
+        queue(&mut queued, parsed(mouse(MouseEventKind::Moved, 4, none), 50), true);
+        assert_eq!(queued.len(), 3);
+        // the latest position and timestamp win, and the merged events are counted
+        assert_eq!((&queued[0].event, queued[0].merged, queued[0].timestamp), (&mouse(MouseEventKind::Moved, 3, none), 2, 30));
+        assert_eq!((&queued[1].event, queued[1].merged), (&key('a'), 0));
+        assert_eq!((&queued[2].event, queued[2].merged), (&mouse(MouseEventKind::Moved, 4, none), 0));
+    }
+
+    #[test]
+    fn no_coalescing_keeps_every_event() {
+        let mut queued = VecDeque::new();
+        for column in 1..=3 {
+            queue(&mut queued, parsed(mouse(MouseEventKind::Moved, column, KeyModifiers::NONE), 0), false);
+        }
+        assert_eq!(queued.len(), 3);
+        assert!(queued.iter().all(|p| p.merged == 0));
+    }
+
+    #[test]
+    fn now_is_monotonic() {
+        let mut last = now();
+        for _ in 0..1000 {
+            let next = now();
+            assert!(next >= last);
+            last = next;
+        }
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(now() - last >= 2_000_000);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn readable_descriptor_is_reported_once_per_batch() {
+        use std::io::Write;
+        use std::os::unix::io::AsRawFd;
+        use std::os::unix::net::UnixStream;
+
+        // waiting needs the terminal, which is missing when the tests run without one
+        if sys::notify().is_err() {
+            return;
+        }
+        let (rx, mut tx) = UnixStream::pair().unwrap();
+        tx.write_all(b"pending").unwrap();
+        let fd = rx.as_raw_fd();
+        register(fd).unwrap();
+        let events = read_batch(10, Duration::from_secs(1));
+        unregister(fd).unwrap();
+        let readable: Vec<Vec<i32>> = events
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| match e.event {
+                SourceEvent::Readable(fds) => Some(fds),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(readable, vec![vec![fd]]);
+    }
+
+    #[test]
+    fn queued_events_keep_their_order_and_timestamps() {
+        let mut queued = VecDeque::new();
+        for c in "abc".chars() {
+            queue(&mut queued, parsed(key(c), now()), true);
+        }
+        let events: Vec<Event> = queued.iter().map(|p| p.event.clone()).collect();
+        assert_eq!(events, vec![key('a'), key('b'), key('c')]);
+        assert!(queued.iter().zip(queued.iter().skip(1)).all(|(a, b)| a.timestamp <= b.timestamp));
+    }
+}
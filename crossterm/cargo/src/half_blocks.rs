@@ -0,0 +1,31 @@
+//! Decoding images, and scaling them down to colors for `▀` half block cells, for terminals
+//! without any graphics protocol.
+
+use image::{imageops, imageops::FilterType, RgbaImage};
+
+use crate::sixel;
+
+/// Decodes a PNG, JPEG or GIF file to RGBA pixels. Only the first frame of an animated GIF is
+/// decoded.
+pub fn decode(data: &[u8]) -> Result<RgbaImage, String> {
+    let decoded = image::load_from_memory(data).map_err(|err| format!("could not decode image: {err}"))?;
+    Ok(decoded.into_rgba8())
+}
+
+/// Scales the image to fit `columns` x `rows` cells, where each cell holds two pixels on top of
+/// each other, keeping its aspect ratio. The result holds two ARGB colors per cell, top then
+/// bottom, row by row. Parts of the cells not covered by the image are fully transparent.
+pub fn half_blocks(image: &RgbaImage, columns: u16, rows: u16) -> Vec<i32> {
+    let (columns, rows) = (u32::from(columns), u32::from(rows));
+    let mut colors = vec![0i32; (columns * rows * 2) as usize];
+    let (width, height) = sixel::fit(image.width(), image.height(), columns, rows * 2);
+    // smoothing keeps detail when scaling down, but would blur small sprites when scaling up
+    let filter = if width < image.width() { FilterType::CatmullRom } else { FilterType::Nearest };
+    let scaled = imageops::resize(image, width, height, filter);
+    for (x, y, pixel) in scaled.enumerate_pixels() {
+        let [r, g, b, a] = pixel.0;
+        let argb = u32::from_be_bytes([a, r, g, b]) as i32;
+        colors[((y / 2 * columns + x) * 2 + y % 2) as usize] = argb;
+    }
+    colors
+}
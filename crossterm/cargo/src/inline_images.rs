@@ -0,0 +1,46 @@
+//! Images through the iTerm2 inline image protocol (`OSC 1337 ; File=`), see
+//! <https://iterm2.com/documentation-images.html>. The terminal decodes the image itself, so any
+//! format it understands works, in full color.
+
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crossterm::Command;
+
+use crate::{osc, terminal_profile};
+
+/// Terminals buffer the whole sequence before decoding it, and drop or choke on very large ones.
+pub const MAX_SIZE: usize = 16 * 1024 * 1024;
+
+/// Shows an image at the cursor position, scaled to `columns` x `rows` cells. The cursor ends up
+/// below the image. Writes nothing if the terminal is not known to support the protocol.
+pub struct InlineImage {
+    pub data: Vec<u8>,
+    pub columns: u16,
+    pub rows: u16,
+    pub preserve_aspect_ratio: bool,
+}
+
+impl Command for InlineImage {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if !terminal_profile::detect().supports_inline_images() {
+            return Ok(());
+        }
+        osc::write_osc(
+            f,
+            &format!(
+                "1337;File=inline=1;size={};width={};height={};preserveAspectRatio={}:{}",
+                self.data.len(),
+                self.columns,
+                self.rows,
+                if self.preserve_aspect_ratio { 1 } else { 0 },
+                STANDARD.encode(&self.data)
+            ),
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("InlineImage")
+    }
+}
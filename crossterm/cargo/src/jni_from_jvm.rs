@@ -1,6 +1,10 @@
-use std::convert::TryInto;
+// the original bindings in this module predate these lints
+#![allow(clippy::needless_return, clippy::needless_question_mark)]
+
+use std::convert::{TryFrom, TryInto};
 use std::io::Write;
 use std::ops::BitOr;
+use std::time::Duration;
 
 use crossterm::{cursor, event, QueueableCommand, style, terminal};
 use jni::{
@@ -11,6 +15,7 @@ use jni::{
     sys::jint,
 };
 
+use crate::{clipboard, commands, inline_images, kitty_graphics, notifications, palette, pixel_mouse, shell_integration, sixel, terminal_state};
 use crate::unify_errors::{UnifiedError, UnifiedResult, UnifyErrors};
 
 fn get_name<'a>(env: JNIEnv<'a>, enum_value: JObject<'a>) -> JniResult<JString<'a>> {
@@ -25,6 +30,10 @@ fn int_field<F>(env: JNIEnv, obj: JObject, name: F) -> JniResult<jint> where F:
     Ok(env.get_field(obj, name, "I")?.i()?)
 }
 
+fn bool_field<F>(env: JNIEnv, obj: JObject, name: F) -> JniResult<bool> where F: Into<JNIString> {
+    env.get_field(obj, name, "Z")?.z()
+}
+
 // Convert Java String to Rust String, properly handling CESU-8 encoding
 // (which Java/JNI uses for characters outside the BMP, like emojis)
 fn as_rust_string(env: JNIEnv, obj: JObject) -> JniResult<String> {
@@ -51,6 +60,110 @@ pub fn to_string(env: JNIEnv, obj: JObject) -> JniResult<String> {
     as_rust_string(env, env.call_method(obj, "toString", "()Ljava/lang/String;", &[])?.l()?)
 }
 
+pub fn duration(env: JNIEnv, obj: JObject) -> JniResult<Duration> {
+    let secs = env.get_field(obj, "secs", "J")?.j()?;
+    let nanos = int_field(env, obj, "nanos")?;
+    Ok(Duration::new(secs.try_into().unwrap_or_default(), nanos.try_into().unwrap_or_default()))
+}
+
+pub fn clipboard_selection(env: JNIEnv, enum_value: JObject) -> JniResult<char> {
+    let java_str = env.get_string(get_name(env, enum_value)?)?;
+
+    fn from_str(str: &str) -> char {
+        match str {
+            "Clipboard" => 'c',
+            "Primary" => 'p',
+            "Secondary" => 'q',
+            other => panic!("not a valid ClipboardSelection: {}", other),
+        }
+    }
+    Ok(from_str(&java_str.to_string_lossy()))
+}
+
+pub fn optional_string(env: JNIEnv, optional_object: JObject) -> JniResult<Option<String>> {
+    let is_empty = env
+        .call_method(optional_object, "isEmpty", "()Z", &[])?
+        .z()?;
+
+    if is_empty {
+        Ok(None)
+    } else {
+        let value = env
+            .call_method(optional_object, "get", "()Ljava/lang/Object;", &[])?
+            .l()?;
+        Ok(Some(as_rust_string(env, value)?))
+    }
+}
+
+pub fn notification_urgency(env: JNIEnv, enum_value: JObject) -> JniResult<notifications::Urgency> {
+    let java_str = env.get_string(get_name(env, enum_value)?)?;
+
+    fn from_str(str: &str) -> notifications::Urgency {
+        match str {
+            "Low" => notifications::Urgency::Low,
+            "Normal" => notifications::Urgency::Normal,
+            "Critical" => notifications::Urgency::Critical,
+            other => panic!("not a valid NotificationUrgency: {}", other),
+        }
+    }
+    Ok(from_str(&java_str.to_string_lossy()))
+}
+
+pub fn progress_state(env: JNIEnv, enum_value: JObject) -> JniResult<commands::ProgressState> {
+    let java_str = env.get_string(get_name(env, enum_value)?)?;
+
+    fn from_str(str: &str) -> commands::ProgressState {
+        match str {
+            "Normal" => commands::ProgressState::Normal,
+            "Error" => commands::ProgressState::Error,
+            "Indeterminate" => commands::ProgressState::Indeterminate,
+            "Paused" => commands::ProgressState::Paused,
+            other => panic!("not a valid ProgressState: {}", other),
+        }
+    }
+    Ok(from_str(&java_str.to_string_lossy()))
+}
+
+pub fn optional_int(env: JNIEnv, optional_int_object: JObject) -> JniResult<Option<i32>> {
+    let is_present = env
+        .call_method(optional_int_object, "isPresent", "()Z", &[])?
+        .z()?;
+
+    if is_present {
+        Ok(Some(env.call_method(optional_int_object, "getAsInt", "()I", &[])?.i()?))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn dynamic_color(env: JNIEnv, enum_value: JObject) -> JniResult<palette::DynamicColor> {
+    let java_str = env.get_string(get_name(env, enum_value)?)?;
+
+    fn from_str(str: &str) -> palette::DynamicColor {
+        match str {
+            "Foreground" => palette::DynamicColor::Foreground,
+            "Background" => palette::DynamicColor::Background,
+            "Cursor" => palette::DynamicColor::Cursor,
+            other => panic!("not a valid DynamicColor: {}", other),
+        }
+    }
+    Ok(from_str(&java_str.to_string_lossy()))
+}
+
+/// Reads the components of a `Color.Rgb` record, checking that they are in range.
+fn rgb(env: JNIEnv, rgb_object: JObject) -> UnifiedResult<(u8, u8, u8)> {
+    let component = |name: &str| {
+        let value = int_field(env, rgb_object, name).unify_errors()?;
+        u8::try_from(value).map_err(|_| UnifiedError::InvalidArgument(format!("{name} must be between 0 and 255, was {value}")))
+    };
+    Ok((component("r")?, component("g")?, component("b")?))
+}
+
+fn palette_index(env: JNIEnv, obj: JObject) -> UnifiedResult<u8> {
+    let index = int_field(env, obj, "index").unify_errors()?;
+    u8::try_from(index).map_err(|_| UnifiedError::InvalidArgument(format!("palette index must be between 0 and 255, was {index}")))
+}
+
 pub fn clear_type(env: JNIEnv, enum_value: JObject) -> JniResult<terminal::ClearType> {
     let javastr: JavaStr = env.get_string(get_name(env, enum_value)?)?;
 
@@ -174,6 +287,15 @@ pub fn attributes_list(env: JNIEnv, attributes_list_obj: JObject) -> JniResult<s
     return Ok(attributes);
 }
 
+pub fn content_style(env: JNIEnv, obj: JObject) -> JniResult<style::ContentStyle> {
+    let foreground_color = optional_color(env, object_field(env, obj, "foreground_color", "Ljava/util/Optional;")?)?;
+    let background_color = optional_color(env, object_field(env, obj, "background_color", "Ljava/util/Optional;")?)?;
+    let underline_color = optional_color(env, object_field(env, obj, "underline_color", "Ljava/util/Optional;")?)?;
+    let attributes_obj = object_field(env, obj, "attributes", "Ltui/crossterm/Attributes;")?;
+    let attributes = attributes_list(env, object_field(env, attributes_obj, "attributes", "Ljava/util/List;")?)?;
+    Ok(style::ContentStyle { foreground_color, background_color, underline_color, attributes })
+}
+
 /// Map the legacy `CursorShape` Java enum (UnderScore, Line, Block) to the closest
 /// equivalent in `crossterm::cursor::SetCursorStyle`. The 0.25 → 0.29 rename collapsed
 /// the old `CursorShape` enum into the `SetCursorStyle` command enum, with separate
@@ -219,6 +341,50 @@ pub fn keyboard_enhancement_flags(
     return Ok(event::KeyboardEnhancementFlags::from_bits_truncate(bits.try_into().unwrap()));
 }
 
+fn byte_array_field<F>(env: JNIEnv, obj: JObject, name: F) -> JniResult<Vec<u8>> where F: Into<JNIString> {
+    let array: JObject = env.get_field(obj, name, "[B")?.l()?;
+    env.convert_byte_array(array.into_raw())
+}
+
+/// Reads a kitty graphics image or placement id, which has to be positive.
+fn kitty_id(env: JNIEnv, obj: JObject, name: &str) -> UnifiedResult<u32> {
+    let id = int_field(env, obj, name).unify_errors()?;
+    if id <= 0 {
+        return Err(UnifiedError::InvalidArgument(format!("{name} must be positive, was {id}")));
+    }
+    Ok(id as u32)
+}
+
+/// Reads `width`, `height` and `pixels` fields describing an RGBA image, four bytes per pixel.
+pub fn rgba_image(env: JNIEnv, obj: JObject) -> UnifiedResult<(u16, u16, Vec<u8>)> {
+    let width = u16_field(env, obj, "width")?;
+    let height = u16_field(env, obj, "height")?;
+    let pixels = byte_array_field(env, obj, "pixels").unify_errors()?;
+    if width == 0 || height == 0 {
+        return Err(UnifiedError::InvalidArgument(format!("image must not be empty, was {width}x{height}")));
+    }
+    let expected = usize::from(width) * usize::from(height) * 4;
+    if pixels.len() != expected {
+        return Err(UnifiedError::InvalidArgument(format!(
+            "expected {expected} bytes of RGBA data for {width}x{height}, was {}",
+            pixels.len()
+        )));
+    }
+    Ok((width, height, pixels))
+}
+
+/// Reads a 0 based, inclusive row range, and checks that it describes at least `min_rows` rows.
+fn row_range(env: JNIEnv, obj: JObject, first: &str, last: &str, min_rows: u16) -> UnifiedResult<(u16, u16)> {
+    let first_row = u16_field(env, obj, first)?;
+    let last_row = u16_field(env, obj, last)?;
+    if last_row < first_row || last_row - first_row + 1 < min_rows {
+        return Err(UnifiedError::InvalidArgument(format!(
+            "{first} ({first_row}) and {last} ({last_row}) must span at least {min_rows} rows"
+        )));
+    }
+    Ok((first_row, last_row))
+}
+
 pub fn queue_commands<W: Write>(w: &mut W, env: JNIEnv, list_obj: JObject) -> UnifiedResult<()> {
     let list = env.get_list(list_obj).unify_errors()?;
     for obj in list.iter().unify_errors()? {
@@ -304,13 +470,45 @@ pub fn queue_command<W: Write>(w: &mut W, env: JNIEnv, obj: JObject) -> UnifiedR
             w.queue(event::EnableMouseCapture).unify_errors()?
         }
         "DisableMouseCapture" => {
+            if std::mem::take(&mut terminal_state::get().pointer_shape) {
+                w.queue(commands::PopPointerShape).unify_errors()?;
+            }
+            if std::mem::take(&mut terminal_state::get().pixel_mouse) {
+                w.queue(pixel_mouse::DisablePixelMouseReporting).unify_errors()?;
+            }
             w.queue(event::DisableMouseCapture).unify_errors()?
         }
+        "EnablePixelMouseReporting" => {
+            let cell_width = u16_field(env, obj, "cell_width")?;
+            let cell_height = u16_field(env, obj, "cell_height")?;
+            if cell_width == 0 || cell_height == 0 {
+                return Err(UnifiedError::InvalidArgument(format!("cell size must not be empty, was {cell_width}x{cell_height} pixels")));
+            }
+            pixel_mouse::set_fallback_cell_size((cell_width, cell_height));
+            terminal_state::get().pixel_mouse = true;
+            w.queue(pixel_mouse::EnablePixelMouseReporting).unify_errors()?
+        }
+        "DisablePixelMouseReporting" => {
+            terminal_state::get().pixel_mouse = false;
+            w.queue(pixel_mouse::DisablePixelMouseReporting).unify_errors()?
+        }
+        "EnableAlternateScroll" => {
+            terminal_state::get().alternate_scroll = true;
+            w.queue(commands::EnableAlternateScroll).unify_errors()?
+        }
+        "DisableAlternateScroll" => {
+            terminal_state::get().alternate_scroll = false;
+            w.queue(commands::DisableAlternateScroll).unify_errors()?
+        }
         "PushKeyboardEnhancementFlags" => {
             let x = keyboard_enhancement_flags(env, object_field(env, obj, "flags", "Ltui/crossterm/KeyboardEnhancementFlags;").unify_errors()?).unify_errors()?;
+            let mut state = terminal_state::get();
+            state.keyboard_enhancement_depth = state.keyboard_enhancement_depth.saturating_add(1);
             w.queue(event::PushKeyboardEnhancementFlags(x)).unify_errors()?
         }
         "PopKeyboardEnhancementFlags" => {
+            let mut state = terminal_state::get();
+            state.keyboard_enhancement_depth = state.keyboard_enhancement_depth.saturating_sub(1);
             w.queue(event::PopKeyboardEnhancementFlags).unify_errors()?
         }
         "EnableFocusChange" => {
@@ -382,6 +580,43 @@ pub fn queue_command<W: Write>(w: &mut W, env: JNIEnv, obj: JObject) -> UnifiedR
             let num_rows = u16_field(env, obj, "num_rows")?;
             w.queue(terminal::ScrollDown(num_rows)).unify_errors()?
         }
+        "SetScrollRegion" => {
+            let (top, bottom) = row_range(env, obj, "top", "bottom", 2)?;
+            w.queue(commands::SetScrollRegion(top, bottom)).unify_errors()?
+        }
+        "ResetScrollRegion" => {
+            w.queue(commands::ResetScrollRegion).unify_errors()?
+        }
+        "ScrollUpInRegion" => {
+            let (first_row, last_row) = row_range(env, obj, "first_row", "last_row", 1)?;
+            let lines_to_scroll = u16_field(env, obj, "lines_to_scroll")?;
+            w.queue(commands::ScrollInRegion { first_row, last_row, lines_to_scroll, up: true }).unify_errors()?
+        }
+        "ScrollDownInRegion" => {
+            let (first_row, last_row) = row_range(env, obj, "first_row", "last_row", 1)?;
+            let lines_to_scroll = u16_field(env, obj, "lines_to_scroll")?;
+            w.queue(commands::ScrollInRegion { first_row, last_row, lines_to_scroll, up: false }).unify_errors()?
+        }
+        "InsertLines" => {
+            let num_lines = u16_field(env, obj, "num_lines")?;
+            w.queue(commands::InsertLines(num_lines)).unify_errors()?
+        }
+        "DeleteLines" => {
+            let num_lines = u16_field(env, obj, "num_lines")?;
+            w.queue(commands::DeleteLines(num_lines)).unify_errors()?
+        }
+        "InsertChars" => {
+            let num_chars = u16_field(env, obj, "num_chars")?;
+            w.queue(commands::InsertChars(num_chars)).unify_errors()?
+        }
+        "DeleteChars" => {
+            let num_chars = u16_field(env, obj, "num_chars")?;
+            w.queue(commands::DeleteChars(num_chars)).unify_errors()?
+        }
+        "EraseChars" => {
+            let num_chars = u16_field(env, obj, "num_chars")?;
+            w.queue(commands::EraseChars(num_chars)).unify_errors()?
+        }
         "Clear" => {
             let x = clear_type(env, object_field(env, obj, "clear_type", "Ltui/crossterm/ClearType;").unify_errors()?).unify_errors()?;
             w.queue(terminal::Clear(x)).unify_errors()?
@@ -395,6 +630,227 @@ pub fn queue_command<W: Write>(w: &mut W, env: JNIEnv, obj: JObject) -> UnifiedR
             let value = str_field(env, obj, "value").unify_errors()?;
             w.queue(style::Print(value)).unify_errors()?
         }
+        "SetClipboard" => {
+            let selection = clipboard_selection(env, object_field(env, obj, "selection", "Ltui/crossterm/ClipboardSelection;").unify_errors()?).unify_errors()?;
+            let text = str_field(env, obj, "text").unify_errors()?;
+            w.queue(clipboard::SetClipboard { selection, text }).unify_errors()?
+        }
+        "Notify" => {
+            let title = str_field(env, obj, "title").unify_errors()?;
+            let body = str_field(env, obj, "body").unify_errors()?;
+            let id = optional_string(env, object_field(env, obj, "id", "Ljava/util/Optional;").unify_errors()?).unify_errors()?;
+            if let Some(id) = id.as_deref().filter(|id| !notifications::is_valid_id(id)) {
+                return Err(UnifiedError::InvalidArgument(format!("not a valid notification id: {id:?}")));
+            }
+            let urgency = notification_urgency(env, object_field(env, obj, "urgency", "Ltui/crossterm/NotificationUrgency;").unify_errors()?).unify_errors()?;
+            w.queue(notifications::Notify { title, body, id, urgency }).unify_errors()?
+        }
+        "SetProgress" => {
+            let state = progress_state(env, object_field(env, obj, "state", "Ltui/crossterm/ProgressState;").unify_errors()?).unify_errors()?;
+            let percent = int_field(env, obj, "percent").unify_errors()?;
+            if !(0..=100).contains(&percent) {
+                return Err(UnifiedError::InvalidArgument(format!("percent must be between 0 and 100, was {percent}")));
+            }
+            terminal_state::get().progress = true;
+            w.queue(commands::SetProgress(state, percent as u8)).unify_errors()?
+        }
+        "ClearProgress" => {
+            terminal_state::get().progress = false;
+            w.queue(commands::ClearProgress).unify_errors()?
+        }
+        "MarkPromptStart" => {
+            w.queue(shell_integration::PromptMark::PromptStart).unify_errors()?
+        }
+        "MarkCommandStart" => {
+            w.queue(shell_integration::PromptMark::CommandStart).unify_errors()?
+        }
+        "MarkOutputStart" => {
+            w.queue(shell_integration::PromptMark::OutputStart).unify_errors()?
+        }
+        "MarkCommandFinished" => {
+            let exit_code = optional_int(env, object_field(env, obj, "exit_code", "Ljava/util/OptionalInt;").unify_errors()?).unify_errors()?;
+            w.queue(shell_integration::PromptMark::CommandFinished(exit_code)).unify_errors()?
+        }
+        "SetWorkingDirectory" => {
+            let path = str_field(env, obj, "path").unify_errors()?;
+            if !shell_integration::is_valid_path(&path) {
+                return Err(UnifiedError::InvalidArgument(format!("working directory must be an absolute path: {path:?}")));
+            }
+            w.queue(shell_integration::SetWorkingDirectory(path)).unify_errors()?
+        }
+        "SetPaletteColor" => {
+            let index = palette_index(env, obj)?;
+            let (r, g, b) = rgb(env, object_field(env, obj, "color", "Ltui/crossterm/Color$Rgb;").unify_errors()?)?;
+            terminal_state::get().palette[usize::from(index)] = true;
+            w.queue(palette::SetPaletteColor { index, r, g, b }).unify_errors()?
+        }
+        "ResetPaletteColor" => {
+            let index = palette_index(env, obj)?;
+            terminal_state::get().palette[usize::from(index)] = false;
+            w.queue(palette::ResetPaletteColors(vec![index])).unify_errors()?
+        }
+        "ResetPalette" => {
+            terminal_state::get().palette = [false; 256];
+            w.queue(palette::ResetPaletteColors(vec![])).unify_errors()?
+        }
+        "SetDynamicColor" => {
+            let which = dynamic_color(env, object_field(env, obj, "which", "Ltui/crossterm/DynamicColor;").unify_errors()?).unify_errors()?;
+            let (r, g, b) = rgb(env, object_field(env, obj, "color", "Ltui/crossterm/Color$Rgb;").unify_errors()?)?;
+            terminal_state::get().dynamic_colors[which.index()] = true;
+            w.queue(palette::SetDynamicColor { which, r, g, b }).unify_errors()?
+        }
+        "ResetDynamicColor" => {
+            let which = dynamic_color(env, object_field(env, obj, "which", "Ltui/crossterm/DynamicColor;").unify_errors()?).unify_errors()?;
+            terminal_state::get().dynamic_colors[which.index()] = false;
+            w.queue(palette::ResetDynamicColor(which)).unify_errors()?
+        }
+        "SetPointerShape" => {
+            let name = str_field(env, obj, "name").unify_errors()?;
+            if !commands::POINTER_SHAPES.contains(&name.as_str()) {
+                return Err(UnifiedError::InvalidArgument(format!("not a valid pointer shape: {name:?}")));
+            }
+            // only the first shape is pushed, so a single pop brings back the original one
+            let push = !std::mem::replace(&mut terminal_state::get().pointer_shape, true);
+            w.queue(commands::SetPointerShape { name, push }).unify_errors()?
+        }
+        "ResetPointerShape" => {
+            // only pop what we pushed, the shape below belongs to the terminal or the shell
+            if std::mem::take(&mut terminal_state::get().pointer_shape) {
+                w.queue(commands::PopPointerShape).unify_errors()?;
+            }
+            w
+        }
+        "DeiconifyWindow" => {
+            w.queue(commands::WindowOp::Deiconify).unify_errors()?
+        }
+        "IconifyWindow" => {
+            w.queue(commands::WindowOp::Iconify).unify_errors()?
+        }
+        "MoveWindow" => {
+            let x = u16_field(env, obj, "x")?;
+            let y = u16_field(env, obj, "y")?;
+            w.queue(commands::WindowOp::Move { x, y }).unify_errors()?
+        }
+        "ResizeWindowPixels" => {
+            let width = u16_field(env, obj, "width")?;
+            let height = u16_field(env, obj, "height")?;
+            w.queue(commands::WindowOp::ResizePixels { width, height }).unify_errors()?
+        }
+        "RaiseWindow" => {
+            w.queue(commands::WindowOp::Raise).unify_errors()?
+        }
+        "LowerWindow" => {
+            w.queue(commands::WindowOp::Lower).unify_errors()?
+        }
+        "MaximizeWindow" => {
+            w.queue(commands::WindowOp::Maximize).unify_errors()?
+        }
+        "UnmaximizeWindow" => {
+            w.queue(commands::WindowOp::Unmaximize).unify_errors()?
+        }
+        "EnterFullscreen" => {
+            w.queue(commands::WindowOp::EnterFullscreen).unify_errors()?
+        }
+        "ExitFullscreen" => {
+            w.queue(commands::WindowOp::ExitFullscreen).unify_errors()?
+        }
+        "ToggleFullscreen" => {
+            w.queue(commands::WindowOp::ToggleFullscreen).unify_errors()?
+        }
+        "SoftReset" => {
+            w.queue(commands::SoftReset).unify_errors()?
+        }
+        "FullReset" => {
+            // RIS resets all of the tracked state in the terminal as well
+            terminal_state::forget();
+            w.queue(commands::FullReset).unify_errors()?
+        }
+        "PrintStyled" => {
+            let text = str_field(env, obj, "text").unify_errors()?;
+            let content_style = content_style(env, object_field(env, obj, "style", "Ltui/crossterm/ContentStyle;").unify_errors()?).unify_errors()?;
+            w.queue(style::PrintStyledContent(style::StyledContent::new(content_style, text))).unify_errors()?
+        }
+        "TransmitRgbaImage" => {
+            let id = kitty_id(env, obj, "image_id")?;
+            let (width, height, pixels) = rgba_image(env, obj)?;
+            let data = kitty_graphics::ImageData::Rgba { width: width.into(), height: height.into(), pixels };
+            terminal_state::get().kitty_images = true;
+            w.queue(kitty_graphics::TransmitImage { id, data }).unify_errors()?
+        }
+        "TransmitPngImage" => {
+            let id = kitty_id(env, obj, "image_id")?;
+            let png = byte_array_field(env, obj, "png").unify_errors()?;
+            if !kitty_graphics::is_png(&png) {
+                return Err(UnifiedError::InvalidArgument("data is not a PNG file".to_string()));
+            }
+            let data = kitty_graphics::ImageData::Png(png);
+            terminal_state::get().kitty_images = true;
+            w.queue(kitty_graphics::TransmitImage { id, data }).unify_errors()?
+        }
+        "PlaceImage" => {
+            let image_id = kitty_id(env, obj, "image_id")?;
+            let placement_id = kitty_id(env, obj, "placement_id")?;
+            let x = u16_field(env, obj, "x")?;
+            let y = u16_field(env, obj, "y")?;
+            let columns = u16_field(env, obj, "columns")?;
+            let rows = u16_field(env, obj, "rows")?;
+            if columns == 0 || rows == 0 {
+                return Err(UnifiedError::InvalidArgument(format!("placement must not be empty, was {columns}x{rows} cells")));
+            }
+            let z_index = int_field(env, obj, "z_index").unify_errors()?;
+            w.queue(cursor::MoveTo(x, y)).unify_errors()?;
+            w.queue(kitty_graphics::PlaceImage { image_id, placement_id, columns, rows, z_index }).unify_errors()?
+        }
+        "DeleteImage" => {
+            let id = kitty_id(env, obj, "image_id")?;
+            w.queue(kitty_graphics::DeleteImages::Image(id)).unify_errors()?
+        }
+        "DeletePlacement" => {
+            let image_id = kitty_id(env, obj, "image_id")?;
+            let placement_id = kitty_id(env, obj, "placement_id")?;
+            w.queue(kitty_graphics::DeleteImages::Placement { image_id, placement_id }).unify_errors()?
+        }
+        "DrawSixel" => {
+            let x = u16_field(env, obj, "x")?;
+            let y = u16_field(env, obj, "y")?;
+            let columns = u16_field(env, obj, "columns")?;
+            let rows = u16_field(env, obj, "rows")?;
+            if columns == 0 || rows == 0 {
+                return Err(UnifiedError::InvalidArgument(format!("target must not be empty, was {columns}x{rows} cells")));
+            }
+            let cell_width = u16_field(env, obj, "cell_width")?;
+            let cell_height = u16_field(env, obj, "cell_height")?;
+            if cell_width == 0 || cell_height == 0 {
+                return Err(UnifiedError::InvalidArgument(format!("cell size must not be empty, was {cell_width}x{cell_height} pixels")));
+            }
+            let (width, height, pixels) = rgba_image(env, obj)?;
+            w.queue(cursor::MoveTo(x, y)).unify_errors()?;
+            w.queue(sixel::DrawSixel { width, height, pixels, columns, rows, cell_width, cell_height }).unify_errors()?
+        }
+        "DrawInlineImage" => {
+            let x = u16_field(env, obj, "x")?;
+            let y = u16_field(env, obj, "y")?;
+            let columns = u16_field(env, obj, "columns")?;
+            let rows = u16_field(env, obj, "rows")?;
+            if columns == 0 || rows == 0 {
+                return Err(UnifiedError::InvalidArgument(format!("target must not be empty, was {columns}x{rows} cells")));
+            }
+            let data = byte_array_field(env, obj, "data").unify_errors()?;
+            if data.is_empty() || data.len() > inline_images::MAX_SIZE {
+                return Err(UnifiedError::InvalidArgument(format!(
+                    "image must be between 1 and {} bytes, was {}",
+                    inline_images::MAX_SIZE,
+                    data.len()
+                )));
+            }
+            let preserve_aspect_ratio = bool_field(env, obj, "preserve_aspect_ratio").unify_errors()?;
+            w.queue(cursor::MoveTo(x, y)).unify_errors()?;
+            w.queue(inline_images::InlineImage { data, columns, rows, preserve_aspect_ratio }).unify_errors()?
+        }
+        "DeleteAllImages" => {
+            terminal_state::get().kitty_images = false;
+            w.queue(kitty_graphics::DeleteImages::All).unify_errors()?
+        }
         other => panic!("Not a valid Command: {}", other),
     };
     Ok(())
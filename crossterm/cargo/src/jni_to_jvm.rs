@@ -1,3 +1,6 @@
+// the original bindings in this module predate these lints
+#![allow(clippy::needless_return, clippy::redundant_static_lifetimes)]
+
 use jni::{
     errors::{Result as JniResult},
     objects::{JClass, JObject, JValue},
@@ -6,6 +9,8 @@ use jni::{
 
 use crossterm::event;
 
+use crate::event_source::{SourceEvent, TimedEvent};
+
 pub fn record<'a>(env: JNIEnv<'a>, class: &str, params_sig: &str, args: &[JValue]) -> JniResult<JObject<'a>> {
     return env
         .new_object(
@@ -21,6 +26,16 @@ pub fn enum_value<'a>(env: JNIEnv<'a>, cls_name: &str, name: &str) -> JniResult<
     return env.get_static_field(cls, name, sig)?.l();
 }
 
+pub fn optional<'a>(env: JNIEnv<'a>, value: Option<JObject<'a>>) -> JniResult<JObject<'a>> {
+    let cls = env.find_class("java/util/Optional")?;
+    match value {
+        Some(value) => env
+            .call_static_method(cls, "of", "(Ljava/lang/Object;)Ljava/util/Optional;", &[JValue::Object(value)])?
+            .l(),
+        None => env.call_static_method(cls, "empty", "()Ljava/util/Optional;", &[])?.l(),
+    }
+}
+
 pub fn xy(env: JNIEnv, x: u16, y: u16) -> JniResult<JObject> {
     return record(
         env,
@@ -30,6 +45,17 @@ pub fn xy(env: JNIEnv, x: u16, y: u16) -> JniResult<JObject> {
     );
 }
 
+pub fn rgba_image<'a>(env: JNIEnv<'a>, image: &image::RgbaImage) -> JniResult<JObject<'a>> {
+    // a local reference which was just created, so it is valid for the lifetime of `env`
+    let pixels = unsafe { JObject::from_raw(env.byte_array_from_slice(image.as_raw())?) };
+    record(
+        env,
+        "tui/crossterm/RgbaImage",
+        "II[B",
+        &[JValue::Int(image.width() as i32), JValue::Int(image.height() as i32), JValue::Object(pixels)],
+    )
+}
+
 pub fn media_key_code(env: JNIEnv, e: event::MediaKeyCode) -> JniResult<JObject> {
     const CLASS_NAME: &'static str = "tui/crossterm/MediaKeyCode";
 
@@ -109,12 +135,11 @@ pub fn key_code(env: JNIEnv, e: event::KeyCode) -> JniResult<JObject> {
             );
         }
         event::KeyCode::Char(c) => {
-            let utf16 = c as u16; // todo
             return record(
                 env,
                 "tui/crossterm/KeyCode$Char",
-                "C",
-                &[JValue::Char(utf16)],
+                "I",
+                &[JValue::Int(c as i32)],
             );
         }
         event::KeyCode::F(n) => {
@@ -224,49 +249,97 @@ pub fn mouse_event_kind(env: JNIEnv, e: event::MouseEventKind) -> JniResult<JObj
     };
 }
 
-pub fn mouse_event(env: JNIEnv, e: event::MouseEvent) -> JniResult<JObject> {
+/// `pixel` is the position in pixels, if the event was reported in pixels and translated to
+/// cells by [`crate::pixel_mouse::translate`].
+pub fn mouse_event(env: JNIEnv, e: event::MouseEvent, pixel: Option<(u16, u16)>) -> JniResult<JObject> {
+    let pixel = match pixel {
+        Some((x, y)) => Some(xy(env, x, y)?),
+        None => None,
+    };
     let args = &[
         JValue::Object(mouse_event_kind(env, e.kind)?),
         JValue::Int(e.column.into()),
         JValue::Int(e.row.into()),
         JValue::Object(key_modifiers(env, e.modifiers)?),
+        JValue::Object(optional(env, pixel)?),
     ];
     return record(
         env,
         "tui/crossterm/MouseEvent",
-        "Ltui/crossterm/MouseEventKind;IILtui/crossterm/KeyModifiers;",
+        "Ltui/crossterm/MouseEventKind;IILtui/crossterm/KeyModifiers;Ljava/util/Optional;",
         args,
     );
 }
 
-pub fn event(env: JNIEnv, e: event::Event) -> JniResult<JObject> {
+/// Converts a terminal event, with `timestamp` from [`crate::event_source::now`] as the last
+/// component of every record. `merged` is only kept for the events which can be coalesced, and
+/// `pixel` only for mouse events.
+pub fn event(env: JNIEnv, e: event::Event, merged: u32, pixel: Option<(u16, u16)>, timestamp: i64) -> JniResult<JObject> {
+    let t = JValue::Long(timestamp);
+    let merged = JValue::Int(merged.min(i32::MAX as u32) as i32);
     return match e {
-        event::Event::FocusGained => record(env, "tui/crossterm/Event$FocusGained", "", &[]),
-        event::Event::FocusLost => record(env, "tui/crossterm/Event$FocusLost", "", &[]),
+        event::Event::FocusGained => record(env, "tui/crossterm/Event$FocusGained", "J", &[t]),
+        event::Event::FocusLost => record(env, "tui/crossterm/Event$FocusLost", "J", &[t]),
         event::Event::Key(ke) => record(
             env,
             "tui/crossterm/Event$Key",
-            "Ltui/crossterm/KeyEvent;",
-            &[JValue::Object(key_event(env, ke)?)],
+            "Ltui/crossterm/KeyEvent;J",
+            &[JValue::Object(key_event(env, ke)?), t],
         ),
         event::Event::Mouse(me) => record(
             env,
             "tui/crossterm/Event$Mouse",
-            "Ltui/crossterm/MouseEvent;",
-            &[JValue::Object(mouse_event(env, me)?)],
+            "Ltui/crossterm/MouseEvent;IJ",
+            &[JValue::Object(mouse_event(env, me, pixel)?), merged, t],
         ),
         event::Event::Paste(str) => record(
             env,
             "tui/crossterm/Event$Paste",
-            "Ljava/lang/String;",
-            &[JValue::Object(env.new_string(str)?.into())],
+            "Ljava/lang/String;J",
+            &[JValue::Object(env.new_string(str)?.into()), t],
         ),
 
         event::Event::Resize(x, y) => record(
             env,
             "tui/crossterm/Event$Resize",
-            "II",
-            &[JValue::Int(x.into()), JValue::Int(y.into())],
+            "IIIJ",
+            &[JValue::Int(x.into()), JValue::Int(y.into()), merged, t],
         ),
     };
 }
+
+pub fn source_event(env: JNIEnv, e: TimedEvent) -> JniResult<JObject> {
+    let t = JValue::Long(e.timestamp);
+    match e.event {
+        SourceEvent::Terminal { event: terminal, merged, pixel } => event(env, terminal, merged, pixel, e.timestamp),
+        SourceEvent::Woken => record(env, "tui/crossterm/Event$Woken", "J", &[t]),
+        SourceEvent::Timer(id) => record(env, "tui/crossterm/Event$Timer", "JJ", &[JValue::Long(id), t]),
+        SourceEvent::Readable(fds) => {
+            let array = env.new_int_array(fds.len() as i32)?;
+            env.set_int_array_region(array, 0, &fds)?;
+            // a local reference which was just created, so it is valid for the lifetime of `env`
+            let array = unsafe { JObject::from_raw(array) };
+            record(env, "tui/crossterm/Event$Readable", "[IJ", &[JValue::Object(array), t])
+        }
+    }
+}
+
+/// The event which tells the consumer of the event reader that reading failed with `message`,
+/// and that no further events follow.
+pub fn reader_failed<'a>(env: JNIEnv<'a>, message: &str, timestamp: i64) -> JniResult<JObject<'a>> {
+    let message = env.new_string(message)?;
+    record(env, "tui/crossterm/Event$ReaderFailed", "Ljava/lang/String;J", &[JValue::Object(message.into()), JValue::Long(timestamp)])
+}
+
+pub fn event_list<'a>(env: JNIEnv<'a>, events: Vec<TimedEvent>) -> JniResult<JObject<'a>> {
+    let list = env.new_object("java/util/ArrayList", "(I)V", &[JValue::Int(events.len() as i32)])?;
+    let jlist = env.get_list(list)?;
+    for e in events {
+        // a burst of events would otherwise pile up local references until we return, so each
+        // event is converted in its own frame and only the result is kept until it is added
+        let obj = env.with_local_frame(16, || source_event(env, e))?;
+        jlist.add(obj)?;
+        env.delete_local_ref(obj)?;
+    }
+    Ok(list)
+}
@@ -38,6 +38,8 @@ impl<T> JvmUnwrapper<T> for Result<T, UnifiedError> where T: Default {
             Err(UnifiedError::Jni(jni_error)) => handle_jni_error(env, jni_error),
             Err(UnifiedError::Io(err)) => handle_error(env, err),
             Err(UnifiedError::NotU16(jint)) => handle_not_u16(env, jint),
+            Err(UnifiedError::InvalidArgument(message)) => handle_invalid_argument(env, message),
+            Err(UnifiedError::IllegalState(message)) => handle_illegal_state(env, message),
         }
     }
 }
@@ -67,3 +69,15 @@ fn handle_not_u16<T>(env: JNIEnv, jint: jint) -> T where T: Default {
     env.throw_new(runtime_exception, format!("{jint:?} is not an u16")).unwrap();
     T::default()
 }
+
+fn handle_invalid_argument<T>(env: JNIEnv, message: String) -> T where T: Default {
+    let illegal_argument_exception = env.find_class("java/lang/IllegalArgumentException").unwrap();
+    env.throw_new(illegal_argument_exception, message).unwrap();
+    T::default()
+}
+
+fn handle_illegal_state<T>(env: JNIEnv, message: String) -> T where T: Default {
+    let illegal_state_exception = env.find_class("java/lang/IllegalStateException").unwrap();
+    env.throw_new(illegal_state_exception, message).unwrap();
+    T::default()
+}
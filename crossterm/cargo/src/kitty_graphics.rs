@@ -0,0 +1,185 @@
+//! Images through the kitty graphics protocol, see
+//! <https://sw.kovidgoyal.net/kitty/graphics-protocol/>.
+//!
+//! Images are transmitted once under an id and can then be placed any number of times. All
+//! commands pass `q=2`, so the terminal does not answer on stdin where the replies would show up
+//! as key events.
+
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crossterm::Command;
+
+use crate::osc;
+
+/// The terminal accepts at most 4096 bytes of base64 per escape sequence.
+const CHUNK_SIZE: usize = 4096;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub enum ImageData {
+    /// Uncompressed pixels, four bytes per pixel, row by row.
+    Rgba { width: u32, height: u32, pixels: Vec<u8> },
+    /// A complete PNG file, which the terminal decodes itself.
+    Png(Vec<u8>),
+}
+
+/// Whether `data` starts like a PNG file.
+pub fn is_png(data: &[u8]) -> bool {
+    data.starts_with(PNG_SIGNATURE)
+}
+
+/// Transmits an image and stores it under `id`, replacing an earlier image with the same id. The
+/// data is sent in chunks, each in its own escape sequence.
+pub struct TransmitImage {
+    pub id: u32,
+    pub data: ImageData,
+}
+
+impl TransmitImage {
+    /// The payloads of the escape sequences, one per chunk.
+    fn payloads(&self) -> Vec<String> {
+        let (control, bytes) = match &self.data {
+            ImageData::Rgba { width, height, pixels } => (format!("a=t,f=32,s={width},v={height},i={},q=2", self.id), pixels),
+            ImageData::Png(data) => (format!("a=t,f=100,i={},q=2", self.id), data),
+        };
+        let encoded = STANDARD.encode(bytes);
+        // base64 is ascii, so the chunks can be cut anywhere
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(CHUNK_SIZE).collect();
+        let mut payloads = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = if i + 1 < chunks.len() { 1 } else { 0 };
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            // only the first chunk carries the control data, the rest only say whether more follow,
+            // and repeat q=2 since every chunk is a command which the terminal might answer
+            if i == 0 {
+                payloads.push(format!("G{control},m={more};{chunk}"));
+            } else {
+                payloads.push(format!("Gm={more},q=2;{chunk}"));
+            }
+        }
+        payloads
+    }
+}
+
+impl Command for TransmitImage {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        for payload in self.payloads() {
+            osc::write_apc(f, &payload)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("TransmitImage")
+    }
+}
+
+/// Places a transmitted image at the cursor position, scaled to `columns` x `rows` cells. Placing
+/// the same image with the same `placement_id` again moves the placement. The cursor does not
+/// move.
+///
+/// Placements with a negative `z_index` are drawn below the text, `-1_073_741_824` and lower even
+/// below cells with a background color.
+pub struct PlaceImage {
+    pub image_id: u32,
+    pub placement_id: u32,
+    pub columns: u16,
+    pub rows: u16,
+    pub z_index: i32,
+}
+
+impl PlaceImage {
+    fn payload(&self) -> String {
+        format!(
+            "Ga=p,i={},p={},c={},r={},z={},C=1,q=2",
+            self.image_id, self.placement_id, self.columns, self.rows, self.z_index
+        )
+    }
+}
+
+impl Command for PlaceImage {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_apc(f, &self.payload())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("PlaceImage")
+    }
+}
+
+pub enum DeleteImages {
+    /// Removes all placements of the image, and frees its data.
+    Image(u32),
+    /// Removes a single placement. The image stays around to be placed again.
+    Placement { image_id: u32, placement_id: u32 },
+    /// Removes all placements of all images, and frees their data.
+    All,
+}
+
+impl DeleteImages {
+    fn payload(&self) -> String {
+        match self {
+            DeleteImages::Image(id) => format!("Ga=d,d=I,i={id},q=2"),
+            DeleteImages::Placement { image_id, placement_id } => format!("Ga=d,d=i,i={image_id},p={placement_id},q=2"),
+            DeleteImages::All => "Ga=d,d=A,q=2".to_string(),
+        }
+    }
+}
+
+impl Command for DeleteImages {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_apc(f, &self.payload())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("DeleteImages")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transmit(data: ImageData) -> Vec<String> {
+        TransmitImage { id: 7, data }.payloads()
+    }
+
+    #[test]
+    fn small_image_is_sent_in_one_chunk() {
+        let payloads = transmit(ImageData::Rgba { width: 1, height: 1, pixels: vec![1, 2, 3, 4] });
+        assert_eq!(payloads, vec!["Ga=t,f=32,s=1,v=1,i=7,q=2,m=0;AQIDBA==".to_string()]);
+    }
+
+    #[test]
+    fn large_image_is_split_into_chunks() {
+        // 3 bytes become 4 base64 characters, so this is 4096 + 4096 + 4 characters
+        let data = vec![0u8; 3 * 2048 + 3];
+        let encoded = STANDARD.encode(&data);
+        let payloads = transmit(ImageData::Png(data));
+        assert_eq!(payloads.len(), 3);
+        assert_eq!(payloads[0], format!("Ga=t,f=100,i=7,q=2,m=1;{}", &encoded[..4096]));
+        assert_eq!(payloads[1], format!("Gm=1,q=2;{}", &encoded[4096..8192]));
+        assert_eq!(payloads[2], format!("Gm=0,q=2;{}", &encoded[8192..]));
+    }
+
+    #[test]
+    fn exact_multiple_of_chunk_size_has_no_empty_chunk() {
+        let payloads = transmit(ImageData::Png(vec![0u8; 3 * 1024]));
+        assert_eq!(payloads.len(), 1);
+        assert!(payloads[0].starts_with("Ga=t,f=100,i=7,q=2,m=0;"));
+        assert_eq!(payloads[0].split_once(';').unwrap().1.len(), CHUNK_SIZE);
+    }
+
+    #[test]
+    fn every_command_is_quiet() {
+        let place = PlaceImage { image_id: 1, placement_id: 2, columns: 3, rows: 4, z_index: -1 };
+        assert_eq!(place.payload(), "Ga=p,i=1,p=2,c=3,r=4,z=-1,C=1,q=2");
+        assert_eq!(DeleteImages::Image(1).payload(), "Ga=d,d=I,i=1,q=2");
+        assert_eq!(DeleteImages::Placement { image_id: 1, placement_id: 2 }.payload(), "Ga=d,d=i,i=1,p=2,q=2");
+        assert_eq!(DeleteImages::All.payload(), "Ga=d,d=A,q=2");
+    }
+}
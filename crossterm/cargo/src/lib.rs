@@ -1,7 +1,24 @@
 extern crate core;
 
 pub mod api;
+pub mod clipboard;
+pub mod commands;
+pub mod event_reader;
+pub mod event_source;
+pub mod half_blocks;
+pub mod inline_images;
 pub mod jni_from_jvm;
 pub mod jni_to_jvm;
 pub mod jvm_unwrapper;
-pub mod unify_errors;
\ No newline at end of file
+pub mod kitty_graphics;
+pub mod notifications;
+pub mod osc;
+pub mod palette;
+pub mod pixel_mouse;
+pub mod shell_integration;
+pub mod sixel;
+pub mod terminal_profile;
+pub mod terminal_state;
+pub mod timers;
+pub mod tty_query;
+pub mod unify_errors;
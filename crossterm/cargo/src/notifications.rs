@@ -0,0 +1,112 @@
+//! Desktop notifications through terminal escape sequences.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crossterm::Command;
+
+use crate::osc;
+use crate::terminal_profile::{self, NotificationProtocol};
+
+pub enum Urgency {
+    Low,
+    Normal,
+    Critical,
+}
+
+/// Shows a desktop notification, using whichever escape sequence the detected terminal
+/// understands. Writes nothing on terminals which are not known to support notifications.
+pub struct Notify {
+    pub title: String,
+    pub body: String,
+    /// Only used by kitty, where a notification with the same id replaces the previous one.
+    pub id: Option<String>,
+    pub urgency: Urgency,
+}
+
+/// Whether `id` is acceptable as a kitty notification identifier.
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || "-_+.".contains(c))
+}
+
+/// Control characters would terminate or corrupt the escape sequence.
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// The OSC 9 payload, which only has room for a single message.
+fn osc9_payload(title: &str, body: &str) -> String {
+    let message = if title.is_empty() {
+        sanitize(body)
+    } else if body.is_empty() {
+        sanitize(title)
+    } else {
+        format!("{}: {}", sanitize(title), sanitize(body))
+    };
+    // ConEmu-style `OSC 9 ; <digit> ;` sequences mean something else entirely
+    match message.as_bytes() {
+        [d, b';', ..] if d.is_ascii_digit() => format!("9; {message}"),
+        _ => format!("9;{message}"),
+    }
+}
+
+impl Command for Notify {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match terminal_profile::detect().notification_protocol() {
+            None => Ok(()),
+            Some(NotificationProtocol::Osc9) => osc::write_osc(f, &osc9_payload(&self.title, &self.body)),
+            Some(NotificationProtocol::Osc777) => {
+                let title = sanitize(&self.title).replace(';', ",");
+                osc::write_osc(f, &format!("777;notify;{};{}", title, sanitize(&self.body)))
+            }
+            Some(NotificationProtocol::Osc99) => {
+                static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+                let id = match &self.id {
+                    Some(id) => id.clone(),
+                    None => format!("jatatui-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+                };
+                let urgency = match self.urgency {
+                    Urgency::Low => 0,
+                    Urgency::Normal => 1,
+                    Urgency::Critical => 2,
+                };
+                // the title is sent unfinished (d=0), so that the body is shown along with it
+                osc::write_osc(f, &format!("99;i={id}:d=0:e=1:u={urgency}:p=title;{}", STANDARD.encode(&self.title)))?;
+                osc::write_osc(f, &format!("99;i={id}:d=1:e=1:p=body;{}", STANDARD.encode(&self.body)))
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("Notify")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_drops_control_characters() {
+        assert_eq!(sanitize("a\x1b]b\x07c\nd\u{9c}e"), "a]bcde");
+        assert_eq!(sanitize("plain text; ünïcode"), "plain text; ünïcode");
+    }
+
+    #[test]
+    fn osc9_payload_joins_title_and_body() {
+        assert_eq!(osc9_payload("Build", "done"), "9;Build: done");
+        assert_eq!(osc9_payload("", "done"), "9;done");
+        assert_eq!(osc9_payload("Build", ""), "9;Build");
+        assert_eq!(osc9_payload("Bu\x07ild", "do\x1bne"), "9;Build: done");
+    }
+
+    #[test]
+    fn osc9_payload_escapes_leading_digit() {
+        assert_eq!(osc9_payload("", "4;3"), "9; 4;3");
+        assert_eq!(osc9_payload("1;x", "y"), "9; 1;x: y");
+        assert_eq!(osc9_payload("", "42;x"), "9;42;x");
+        assert_eq!(osc9_payload("", "\n4;3"), "9; 4;3");
+    }
+}
@@ -0,0 +1,39 @@
+//! Helpers for writing OSC (operating system command) and APC (application program command)
+//! sequences.
+
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Whether we are running inside tmux, which swallows OSC and APC sequences it doesn't know about unless
+/// they are wrapped in a DCS passthrough.
+pub fn in_tmux() -> bool {
+    static IN_TMUX: OnceLock<bool> = OnceLock::new();
+    *IN_TMUX.get_or_init(|| env::var_os("TMUX").is_some_and(|v| !v.is_empty()))
+}
+
+/// Writes `ESC ] payload ST`, wrapped for tmux passthrough when running inside tmux.
+pub fn write_osc(f: &mut impl fmt::Write, payload: &str) -> fmt::Result {
+    write_string(f, ']', payload)
+}
+
+/// Writes `ESC _ payload ST`, wrapped for tmux passthrough when running inside tmux.
+pub fn write_apc(f: &mut impl fmt::Write, payload: &str) -> fmt::Result {
+    write_string(f, '_', payload)
+}
+
+fn write_string(f: &mut impl fmt::Write, introducer: char, payload: &str) -> fmt::Result {
+    if in_tmux() {
+        // every ESC inside the passthrough has to be doubled
+        write!(f, "\x1bPtmux;\x1b\x1b{}{}\x1b\x1b\\\x1b\\", introducer, payload)
+    } else {
+        write!(f, "\x1b{}{}\x1b\\", introducer, payload)
+    }
+}
+
+/// Same as [`write_osc`], but returns the sequence.
+pub fn osc(payload: &str) -> String {
+    let mut s = String::new();
+    write_osc(&mut s, payload).unwrap();
+    s
+}
@@ -0,0 +1,105 @@
+//! Redefining the terminal palette: the 256 indexed colors (`OSC 4`), and the default foreground,
+//! default background and cursor colors (`OSC 10`, `11` and `12`).
+
+use std::fmt;
+
+use crossterm::Command;
+
+use crate::osc;
+
+#[derive(Clone, Copy)]
+pub enum DynamicColor {
+    Foreground,
+    Background,
+    Cursor,
+}
+
+impl DynamicColor {
+    pub const ALL: [DynamicColor; 3] = [DynamicColor::Foreground, DynamicColor::Background, DynamicColor::Cursor];
+
+    fn code(self) -> u8 {
+        match self {
+            DynamicColor::Foreground => 10,
+            DynamicColor::Background => 11,
+            DynamicColor::Cursor => 12,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        usize::from(self.code() - 10)
+    }
+}
+
+fn spec(r: u8, g: u8, b: u8) -> String {
+    format!("rgb:{r:02x}/{g:02x}/{b:02x}")
+}
+
+/// Redefines one of the 256 indexed colors.
+pub struct SetPaletteColor {
+    pub index: u8,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Command for SetPaletteColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, &format!("4;{};{}", self.index, spec(self.r, self.g, self.b)))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("SetPaletteColor")
+    }
+}
+
+/// Resets the given indexed colors, or the whole palette if empty.
+pub struct ResetPaletteColors(pub Vec<u8>);
+
+impl Command for ResetPaletteColors {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut payload = String::from("104");
+        for index in &self.0 {
+            payload.push_str(&format!(";{index}"));
+        }
+        osc::write_osc(f, &payload)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("ResetPaletteColors")
+    }
+}
+
+/// Redefines the default foreground, default background or cursor color.
+pub struct SetDynamicColor {
+    pub which: DynamicColor,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Command for SetDynamicColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, &format!("{};{}", self.which.code(), spec(self.r, self.g, self.b)))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("SetDynamicColor")
+    }
+}
+
+/// Resets the default foreground, default background or cursor color (`OSC 110`, `111`, `112`).
+pub struct ResetDynamicColor(pub DynamicColor);
+
+impl Command for ResetDynamicColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, &format!("1{}", self.0.code()))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("ResetDynamicColor")
+    }
+}
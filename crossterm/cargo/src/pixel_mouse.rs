@@ -0,0 +1,72 @@
+//! SGR-pixel mouse reporting (DEC mode 1016), where mouse reports carry pixel coordinates.
+//!
+//! The reports look exactly like SGR cell reports, so crossterm parses them as cells. While the
+//! mode is enabled we take the coordinates as pixels instead, and derive the cell from the size
+//! of a cell in pixels. That comes from the window size in pixels, or from the cell size passed
+//! when the mode was enabled if the window size in pixels is not available.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use crossterm::{event, terminal, Command};
+
+use crate::{sixel, terminal_state};
+
+pub struct EnablePixelMouseReporting;
+
+impl Command for EnablePixelMouseReporting {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[?1016h")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("EnablePixelMouseReporting")
+    }
+}
+
+pub struct DisablePixelMouseReporting;
+
+impl Command for DisablePixelMouseReporting {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[?1016l")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("DisablePixelMouseReporting")
+    }
+}
+
+/// The cell size to use while the window size in pixels is not available.
+static FALLBACK_CELL_SIZE: Mutex<Option<(u16, u16)>> = Mutex::new(None);
+
+/// Sets the cell size to fall back to while pixel reporting is enabled, as passed with the
+/// command which enabled it.
+pub fn set_fallback_cell_size(cell_size: (u16, u16)) {
+    *FALLBACK_CELL_SIZE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(cell_size);
+}
+
+/// If pixel reporting is enabled, replaces the coordinates of `e` with the cell they fall in, and
+/// returns the original pixel coordinates. Called as events are parsed, so they are translated
+/// with the mode and window size in effect when they arrived.
+pub fn translate(e: &mut event::MouseEvent) -> Option<(u16, u16)> {
+    if !terminal_state::get().pixel_mouse {
+        return None;
+    }
+    let pixel = (e.column, e.row);
+    let size = terminal::window_size().ok();
+    let (cell_width, cell_height) = match &size {
+        Some(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            ((size.width / size.columns).max(1), (size.height / size.rows).max(1))
+        }
+        _ => FALLBACK_CELL_SIZE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).unwrap_or(sixel::FALLBACK_CELL_SIZE),
+    };
+    let (columns, rows) = match &size {
+        Some(size) if size.columns > 0 && size.rows > 0 => (size.columns, size.rows),
+        _ => (u16::MAX, u16::MAX),
+    };
+    e.column = (pixel.0 / cell_width).min(columns - 1);
+    e.row = (pixel.1 / cell_height).min(rows - 1);
+    Some(pixel)
+}
@@ -0,0 +1,113 @@
+//! Shell integration marks, which let terminals navigate between prompts and command output in
+//! the scrollback, and open new tabs in the current working directory.
+
+use std::fmt;
+use std::path::Path;
+
+use crossterm::Command;
+
+use crate::osc;
+
+/// The `OSC 133` semantic prompt marks, as emitted by shells around each prompt and command.
+pub enum PromptMark {
+    /// `A`: a prompt starts here.
+    PromptStart,
+    /// `B`: the prompt ended, and the command typed by the user starts here.
+    CommandStart,
+    /// `C`: the command was submitted, and its output starts here.
+    OutputStart,
+    /// `D`: the command finished, optionally with an exit code.
+    CommandFinished(Option<i32>),
+}
+
+impl Command for PromptMark {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            PromptMark::PromptStart => osc::write_osc(f, "133;A"),
+            PromptMark::CommandStart => osc::write_osc(f, "133;B"),
+            PromptMark::OutputStart => osc::write_osc(f, "133;C"),
+            PromptMark::CommandFinished(None) => osc::write_osc(f, "133;D"),
+            PromptMark::CommandFinished(Some(exit_code)) => osc::write_osc(f, &format!("133;D;{exit_code}")),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("PromptMark")
+    }
+}
+
+/// Reports the working directory with `OSC 7 ; file://host/path`.
+pub struct SetWorkingDirectory(pub String);
+
+impl Command for SetWorkingDirectory {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        osc::write_osc(f, &format!("7;file://{}{}", percent_encode(&hostname()), percent_encode(&url_path(&self.0))))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("SetWorkingDirectory")
+    }
+}
+
+/// Windows paths like `C:\Users` become `/C:/Users`.
+fn url_path(path: &str) -> String {
+    if cfg!(windows) {
+        let path = path.replace('\\', "/");
+        if path.starts_with('/') { path } else { format!("/{path}") }
+    } else {
+        path.to_string()
+    }
+}
+
+/// Whether `path` is something we can report as the working directory.
+pub fn is_valid_path(path: &str) -> bool {
+    Path::new(path).is_absolute()
+}
+
+/// Percent-encodes everything except unreserved characters, `/` and the `:` of drive letters.
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => encoded.push(b as char),
+            _ => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    let res = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+    if res != 0 {
+        return String::new();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(windows)]
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_encode_keeps_unreserved_characters() {
+        assert_eq!(percent_encode("/home/user-1/a_b.c~"), "/home/user-1/a_b.c~");
+        assert_eq!(percent_encode("/C:/Users"), "/C:/Users");
+    }
+
+    #[test]
+    fn percent_encode_escapes_everything_else() {
+        assert_eq!(percent_encode("/my dir/100%"), "/my%20dir/100%25");
+        assert_eq!(percent_encode("/a?b#c;d\x07"), "/a%3Fb%23c%3Bd%07");
+        assert_eq!(percent_encode("/ä"), "/%C3%A4");
+    }
+}
@@ -0,0 +1,351 @@
+//! Images as sixel graphics, for terminals without the kitty graphics protocol (xterm, foot,
+//! mlterm, WezTerm and others).
+//!
+//! The image is scaled to fit the target cells, reduced to at most 256 colors with a median cut,
+//! and dithered with Floyd-Steinberg before it is encoded.
+
+use std::fmt;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crossterm::{terminal, Command};
+
+use crate::tty_query;
+
+/// Most sixel terminals support 256 color registers, more than that is rarely available.
+const MAX_COLORS: usize = 256;
+
+/// Used when the terminal tells us neither its size in pixels nor its cell size.
+pub const FALLBACK_CELL_SIZE: (u16, u16) = (10, 20);
+
+/// Pixels with less alpha are left transparent, sixel has no partial transparency.
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Draws an RGBA image at the cursor position, scaled to fit `columns` x `rows` cells of
+/// `cell_width` x `cell_height` pixels while keeping its aspect ratio. The cursor position is
+/// undefined afterwards.
+pub struct DrawSixel {
+    pub width: u16,
+    pub height: u16,
+    pub pixels: Vec<u8>,
+    pub columns: u16,
+    pub rows: u16,
+    pub cell_width: u16,
+    pub cell_height: u16,
+}
+
+impl Command for DrawSixel {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let max_width = u32::from(self.columns) * u32::from(self.cell_width);
+        let max_height = u32::from(self.rows) * u32::from(self.cell_height);
+        let (width, height) = fit(u32::from(self.width), u32::from(self.height), max_width, max_height);
+        let pixels = scale(&self.pixels, u32::from(self.width), u32::from(self.height), width, height);
+        let palette = median_cut(&pixels);
+        let indexed = dither(&pixels, width as usize, &palette);
+        f.write_str(&encode(&indexed, width as usize, height as usize, &palette))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        crate::commands::unsupported_winapi("DrawSixel")
+    }
+}
+
+/// The size of a cell in pixels. Asks the terminal if the pixel size of the window is not
+/// available, and falls back to [`FALLBACK_CELL_SIZE`] if it does not answer.
+pub fn cell_size() -> (u16, u16) {
+    if let Ok(size) = terminal::window_size() {
+        if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 {
+            return ((size.width / size.columns).max(1), (size.height / size.rows).max(1));
+        }
+    }
+    // XTWINOPS 16 reports the cell size as `CSI 6 ; height ; width t`
+    let find_reply = |input: &[u8]| {
+        let start = input.windows(4).position(|w| w == b"\x1b[6;")?;
+        let end = input[start..].iter().position(|&b| b == b't')?;
+        Some(start..start + end + 1)
+    };
+    let reply = match tty_query::query("\x1b[16t", Duration::from_millis(100), find_reply) {
+        Ok(Some(reply)) => reply,
+        _ => return FALLBACK_CELL_SIZE,
+    };
+    let reply = String::from_utf8_lossy(&reply);
+    let parsed = reply
+        .strip_prefix("\x1b[6;")
+        .and_then(|rest| rest.strip_suffix('t'))
+        .and_then(|rest| rest.split_once(';'))
+        .and_then(|(h, w)| Some((w.parse::<u16>().ok()?, h.parse::<u16>().ok()?)));
+    match parsed {
+        Some((w, h)) if w > 0 && h > 0 => (w, h),
+        _ => FALLBACK_CELL_SIZE,
+    }
+}
+
+/// The largest size with the aspect ratio of `width` x `height` which fits the bounds.
+pub fn fit(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    // compare max_width / width with max_height / height without dividing
+    if u64::from(max_width) * u64::from(height) <= u64::from(max_height) * u64::from(width) {
+        let h = (u64::from(height) * u64::from(max_width) / u64::from(width)) as u32;
+        (max_width, h.max(1))
+    } else {
+        let w = (u64::from(width) * u64::from(max_height) / u64::from(height)) as u32;
+        (w.max(1), max_height)
+    }
+}
+
+/// Resizes an RGBA image. Each target pixel is the average of the source pixels it covers,
+/// weighted by alpha, which is a nearest neighbour lookup when scaling up.
+fn scale(pixels: &[u8], width: u32, height: u32, new_width: u32, new_height: u32) -> Vec<[u8; 4]> {
+    let span = |i: u32, from: u32, to: u32| {
+        let start = (u64::from(i) * u64::from(from) / u64::from(to)) as usize;
+        let end = ((u64::from(i + 1) * u64::from(from)).div_ceil(u64::from(to)) as usize).max(start + 1);
+        start..end
+    };
+    let mut out = Vec::with_capacity((new_width * new_height) as usize);
+    for y in 0..new_height {
+        let ys = span(y, height, new_height);
+        for x in 0..new_width {
+            let xs = span(x, width, new_width);
+            let (mut r, mut g, mut b, mut a, mut n) = (0u64, 0u64, 0u64, 0u64, 0u64);
+            for sy in ys.clone() {
+                for sx in xs.clone() {
+                    let i = (sy * width as usize + sx) * 4;
+                    let alpha = u64::from(pixels[i + 3]);
+                    r += u64::from(pixels[i]) * alpha;
+                    g += u64::from(pixels[i + 1]) * alpha;
+                    b += u64::from(pixels[i + 2]) * alpha;
+                    a += alpha;
+                    n += 1;
+                }
+            }
+            // fully transparent if no pixel had any alpha
+            out.push(match (r.checked_div(a), g.checked_div(a), b.checked_div(a)) {
+                (Some(r), Some(g), Some(b)) => [r as u8, g as u8, b as u8, (a / n) as u8],
+                _ => [0, 0, 0, 0],
+            });
+        }
+    }
+    out
+}
+
+/// Picks a palette for the opaque pixels by repeatedly splitting the box of colors with the
+/// widest channel range at its median, and averaging each box.
+fn median_cut(pixels: &[[u8; 4]]) -> Vec<[u8; 3]> {
+    let mut colors: Vec<[u8; 3]> = pixels.iter().filter(|p| p[3] >= ALPHA_THRESHOLD).map(|p| [p[0], p[1], p[2]]).collect();
+    colors.sort_unstable();
+    let mut unique = colors.clone();
+    unique.dedup();
+    if unique.len() <= MAX_COLORS {
+        return unique;
+    }
+
+    let range = |colors: &[[u8; 3]]| {
+        (0..3)
+            .map(|c| {
+                let (min, max) = colors.iter().fold((u8::MAX, u8::MIN), |(min, max), p| (min.min(p[c]), max.max(p[c])));
+                (max - min, c)
+            })
+            .max()
+            .unwrap()
+    };
+    let mut boxes = vec![colors];
+    while boxes.len() < MAX_COLORS {
+        let candidate = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| (range(b), i))
+            .filter(|((width, _), _)| *width > 0)
+            .max();
+        let ((_, channel), i) = match candidate {
+            Some(candidate) => candidate,
+            None => break,
+        };
+        let mut split = boxes.swap_remove(i);
+        split.sort_unstable_by_key(|p| p[channel]);
+        let upper = split.split_off(split.len() / 2);
+        boxes.push(split);
+        boxes.push(upper);
+    }
+    boxes
+        .iter()
+        .map(|b| {
+            let sum = b.iter().fold([0usize; 3], |s, p| [s[0] + p[0] as usize, s[1] + p[1] as usize, s[2] + p[2] as usize]);
+            [(sum[0] / b.len()) as u8, (sum[1] / b.len()) as u8, (sum[2] / b.len()) as u8]
+        })
+        .collect()
+}
+
+/// Maps every pixel to a palette index with Floyd-Steinberg dithering. Transparent pixels become
+/// `None`, and neither receive nor spread any error.
+fn dither(pixels: &[[u8; 4]], width: usize, palette: &[[u8; 3]]) -> Vec<Option<u8>> {
+    let mut errors = vec![[0i32; 3]; pixels.len()];
+    let mut out = Vec::with_capacity(pixels.len());
+    for (i, p) in pixels.iter().enumerate() {
+        if p[3] < ALPHA_THRESHOLD {
+            out.push(None);
+            continue;
+        }
+        let wanted = [0, 1, 2].map(|c| (i32::from(p[c]) + errors[i][c] / 16).clamp(0, 255));
+        let index = nearest(palette, wanted);
+        out.push(Some(index as u8));
+        let error = [0, 1, 2].map(|c| wanted[c] - i32::from(palette[index][c]));
+        let x = i % width;
+        let mut spread = |j: usize, weight: i32| {
+            if j < pixels.len() {
+                for c in 0..3 {
+                    errors[j][c] += error[c] * weight;
+                }
+            }
+        };
+        if x + 1 < width {
+            spread(i + 1, 7);
+            spread(i + width + 1, 1);
+        }
+        if x > 0 {
+            spread(i + width - 1, 3);
+        }
+        spread(i + width, 5);
+    }
+    out
+}
+
+fn nearest(palette: &[[u8; 3]], color: [i32; 3]) -> usize {
+    let mut best = (i32::MAX, 0);
+    for (i, p) in palette.iter().enumerate() {
+        let d = (0..3).map(|c| (color[c] - i32::from(p[c])).pow(2)).sum::<i32>();
+        if d < best.0 {
+            best = (d, i);
+            if d == 0 {
+                break;
+            }
+        }
+    }
+    best.1
+}
+
+/// Encodes indexed pixels as a sixel DCS sequence. Each band of six rows is written once per
+/// color it uses, with run length encoding.
+fn encode(indexed: &[Option<u8>], width: usize, height: usize, palette: &[[u8; 3]]) -> String {
+    let mut out = String::new();
+    // P2 = 1 leaves pixels which are not set alone, so transparency works
+    write!(out, "\x1bP0;1;0q\"1;1;{width};{height}").unwrap();
+    for (i, [r, g, b]) in palette.iter().enumerate() {
+        let percent = |v: u8| (u32::from(v) * 100 + 127) / 255;
+        write!(out, "#{i};2;{};{};{}", percent(*r), percent(*g), percent(*b)).unwrap();
+    }
+    let mut sixels = vec![0u8; width];
+    for band in (0..height).step_by(6) {
+        let rows = band..(band + 6).min(height);
+        let mut used = [false; MAX_COLORS];
+        for y in rows.clone() {
+            for index in indexed[y * width..(y + 1) * width].iter().flatten() {
+                used[usize::from(*index)] = true;
+            }
+        }
+        let mut first = true;
+        for color in (0..palette.len()).filter(|&c| used[c]) {
+            for (x, sixel) in sixels.iter_mut().enumerate() {
+                *sixel = rows
+                    .clone()
+                    .filter(|y| indexed[y * width + x] == Some(color as u8))
+                    .fold(0, |bits, y| bits | 1 << (y - band));
+            }
+            // trailing empty sixels are implied by the carriage return
+            let len = sixels.iter().rposition(|&s| s != 0).map_or(0, |i| i + 1);
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            write!(out, "#{color}").unwrap();
+            let mut x = 0;
+            while x < len {
+                let run = sixels[x..len].iter().take_while(|&&s| s == sixels[x]).count();
+                let c = char::from(63 + sixels[x]);
+                if run > 3 {
+                    write!(out, "!{run}{c}").unwrap();
+                } else {
+                    (0..run).for_each(|_| out.push(c));
+                }
+                x += run;
+            }
+        }
+        if rows.end < height {
+            out.push('-');
+        }
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_keeps_aspect_ratio() {
+        assert_eq!(fit(100, 50, 40, 40), (40, 20));
+        assert_eq!(fit(50, 100, 40, 40), (20, 40));
+        assert_eq!(fit(10, 10, 40, 20), (20, 20));
+        // never collapses to nothing
+        assert_eq!(fit(1000, 1, 10, 10), (10, 1));
+    }
+
+    #[test]
+    fn scale_averages_weighted_by_alpha() {
+        let pixels = [255, 0, 0, 255, 0, 0, 255, 255, 0, 255, 0, 0, 0, 0, 0, 0];
+        assert_eq!(scale(&pixels, 2, 2, 1, 1), vec![[127, 0, 127, 127]]);
+        assert_eq!(scale(&[0, 0, 0, 0], 1, 1, 1, 1), vec![[0, 0, 0, 0]]);
+        assert_eq!(scale(&[1, 2, 3, 255], 1, 1, 2, 1), vec![[1, 2, 3, 255], [1, 2, 3, 255]]);
+    }
+
+    #[test]
+    fn median_cut_keeps_few_colors_exactly() {
+        let pixels = [[9, 9, 9, 255], [1, 2, 3, 255], [9, 9, 9, 255], [5, 5, 5, 0]];
+        assert_eq!(median_cut(&pixels), vec![[1, 2, 3], [9, 9, 9]]);
+    }
+
+    #[test]
+    fn median_cut_reduces_many_colors() {
+        let pixels: Vec<[u8; 4]> = (0..512).map(|i| [(i / 2) as u8, (i % 2 * 255) as u8, 0, 255]).collect();
+        let palette = median_cut(&pixels);
+        assert_eq!(palette.len(), MAX_COLORS);
+        // the green channel is the widest, so it is split first
+        assert!(palette.iter().any(|p| p[1] == 0) && palette.iter().any(|p| p[1] == 255));
+    }
+
+    #[test]
+    fn dither_spreads_the_error() {
+        let palette = [[0, 0, 0], [255, 255, 255]];
+        let gray = [[128, 128, 128, 255]; 4];
+        assert_eq!(dither(&gray, 4, &palette), vec![Some(1), Some(0), Some(1), Some(0)]);
+        let exact = [[255, 255, 255, 255], [0, 0, 0, 255], [0, 0, 0, 0]];
+        assert_eq!(dither(&exact, 3, &palette), vec![Some(1), Some(0), None]);
+    }
+
+    #[test]
+    fn encode_single_pixel() {
+        assert_eq!(encode(&[Some(0), None], 2, 1, &[[255, 0, 0]]), "\x1bP0;1;0q\"1;1;2;1#0;2;100;0;0#0@\x1b\\");
+    }
+
+    #[test]
+    fn encode_bands_colors_and_runs() {
+        // a row of color 0 with color 1 below its end, and color 1 again in the second band
+        let mut indexed = vec![None; 5 * 7];
+        indexed[..5].fill(Some(0));
+        indexed[5 + 4] = Some(1);
+        indexed[6 * 5] = Some(1);
+        assert_eq!(
+            encode(&indexed, 5, 7, &[[0, 0, 0], [255, 255, 255]]),
+            "\x1bP0;1;0q\"1;1;5;7#0;2;0;0;0#1;2;100;100;100#0!5@$#1!4?A-#1@\x1b\\"
+        );
+    }
+
+    #[test]
+    fn draw_sixel_fits_cells() {
+        let mut s = String::new();
+        let image = DrawSixel { width: 1, height: 1, pixels: vec![0, 0, 255, 255], columns: 1, rows: 1, cell_width: 2, cell_height: 1 };
+        image.write_ansi(&mut s).unwrap();
+        assert_eq!(s, "\x1bP0;1;0q\"1;1;1;1#0;2;0;0;100#0@\x1b\\");
+    }
+}
@@ -0,0 +1,112 @@
+//! Best-effort detection of which terminal emulator we are running in, for the escape sequences
+//! which are not universally supported.
+//!
+//! Detection is based on the environment variables terminals set for their child processes. Some
+//! of these do not survive `ssh` or `sudo`, in which case we end up with [`Terminal::Unknown`].
+
+use std::env;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terminal {
+    Kitty,
+    ITerm2,
+    WezTerm,
+    Ghostty,
+    Foot,
+    Rxvt,
+    WindowsTerminal,
+    ConEmu,
+    Konsole,
+    Vte,
+    VsCode,
+    AppleTerminal,
+    Xterm,
+    Unknown,
+}
+
+/// Which escape sequence a terminal uses for desktop notifications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationProtocol {
+    /// `OSC 9 ; message`
+    Osc9,
+    /// `OSC 777 ; notify ; title ; body`
+    Osc777,
+    /// `OSC 99 ; metadata ; payload`, kitty's protocol
+    Osc99,
+}
+
+impl Terminal {
+    /// Whether the terminal shows `OSC 9 ; 4` progress reports in its tab or taskbar. Other
+    /// terminals may mistake them for `OSC 9` notifications.
+    pub fn supports_progress(self) -> bool {
+        matches!(self, Terminal::WindowsTerminal | Terminal::ConEmu | Terminal::Ghostty | Terminal::ITerm2)
+    }
+
+    /// Whether the terminal shows `OSC 1337 ; File=` inline images.
+    pub fn supports_inline_images(self) -> bool {
+        matches!(self, Terminal::ITerm2 | Terminal::WezTerm)
+    }
+
+    pub fn notification_protocol(self) -> Option<NotificationProtocol> {
+        match self {
+            Terminal::Kitty => Some(NotificationProtocol::Osc99),
+            Terminal::ITerm2 | Terminal::WezTerm | Terminal::Ghostty => Some(NotificationProtocol::Osc9),
+            Terminal::Foot | Terminal::Rxvt | Terminal::Vte => Some(NotificationProtocol::Osc777),
+            _ => None,
+        }
+    }
+}
+
+pub fn detect() -> Terminal {
+    static TERMINAL: OnceLock<Terminal> = OnceLock::new();
+    *TERMINAL.get_or_init(detect_uncached)
+}
+
+fn detect_uncached() -> Terminal {
+    let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+    let term = var("TERM").unwrap_or_default();
+    if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" {
+        return Terminal::Kitty;
+    }
+    match var("TERM_PROGRAM").as_deref() {
+        Some("iTerm.app") => return Terminal::ITerm2,
+        Some("WezTerm") => return Terminal::WezTerm,
+        Some("ghostty") => return Terminal::Ghostty,
+        Some("vscode") => return Terminal::VsCode,
+        Some("Apple_Terminal") => return Terminal::AppleTerminal,
+        _ => {}
+    }
+    // iTerm2 forwards this one over ssh
+    if var("LC_TERMINAL").as_deref() == Some("iTerm2") {
+        return Terminal::ITerm2;
+    }
+    if var("WEZTERM_EXECUTABLE").is_some() {
+        return Terminal::WezTerm;
+    }
+    if term == "xterm-ghostty" {
+        return Terminal::Ghostty;
+    }
+    if term.starts_with("foot") {
+        return Terminal::Foot;
+    }
+    if term.starts_with("rxvt") {
+        return Terminal::Rxvt;
+    }
+    if var("WT_SESSION").is_some() {
+        return Terminal::WindowsTerminal;
+    }
+    if var("ConEmuANSI").as_deref() == Some("ON") {
+        return Terminal::ConEmu;
+    }
+    if var("KONSOLE_VERSION").is_some() {
+        return Terminal::Konsole;
+    }
+    if var("VTE_VERSION").is_some() {
+        return Terminal::Vte;
+    }
+    if var("XTERM_VERSION").is_some() {
+        return Terminal::Xterm;
+    }
+    Terminal::Unknown
+}
@@ -0,0 +1,113 @@
+//! Terminal state which was changed through commands and has to be undone before the application
+//! exits, because the terminal keeps it after we are gone.
+
+use std::io::{self, Write};
+use std::sync::{Mutex, MutexGuard};
+
+use crossterm::{cursor, event, style, terminal, QueueableCommand};
+
+use crate::{commands, kitty_graphics, palette, pixel_mouse};
+
+pub struct TerminalState {
+    /// A progress indicator is shown in the tab or taskbar.
+    pub progress: bool,
+    /// Indexed colors which were redefined.
+    pub palette: [bool; 256],
+    /// Dynamic colors which were redefined, indexed by [`palette::DynamicColor::index`].
+    pub dynamic_colors: [bool; 3],
+    /// A mouse pointer shape was pushed, with the original shape kept below it.
+    pub pointer_shape: bool,
+    /// How many levels of keyboard enhancement flags were pushed and not yet popped.
+    pub keyboard_enhancement_depth: u16,
+    /// Mouse reports carry pixel coordinates (DEC mode 1016).
+    pub pixel_mouse: bool,
+    /// Wheel scrolls are turned into arrow keys on the alternate screen (DEC mode 1007).
+    pub alternate_scroll: bool,
+    /// Images were transmitted through the kitty graphics protocol, and may still be shown.
+    pub kitty_images: bool,
+}
+
+/// Nothing changed, as the terminal was before we started, or after a full reset.
+const INITIAL: TerminalState = TerminalState {
+    progress: false,
+    palette: [false; 256],
+    dynamic_colors: [false; 3],
+    pointer_shape: false,
+    keyboard_enhancement_depth: 0,
+    pixel_mouse: false,
+    alternate_scroll: false,
+    kitty_images: false,
+};
+
+static STATE: Mutex<TerminalState> = Mutex::new(INITIAL);
+
+pub fn get() -> MutexGuard<'static, TerminalState> {
+    // the state is always consistent, so a panic while holding the lock does not matter
+    STATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Forgets about everything, after the terminal was told to reset all of it.
+pub fn forget() {
+    *get() = INITIAL;
+}
+
+/// Queues the commands which undo everything still in effect, and forgets about it.
+pub fn restore<W: Write>(w: &mut W) -> io::Result<()> {
+    let mut state = get();
+    if state.progress {
+        w.queue(commands::ClearProgress)?;
+        state.progress = false;
+    }
+    let changed: Vec<u8> = (0..=255u8).filter(|&i| state.palette[usize::from(i)]).collect();
+    if !changed.is_empty() {
+        w.queue(palette::ResetPaletteColors(changed))?;
+        state.palette = [false; 256];
+    }
+    for which in palette::DynamicColor::ALL {
+        if state.dynamic_colors[which.index()] {
+            w.queue(palette::ResetDynamicColor(which))?;
+            state.dynamic_colors[which.index()] = false;
+        }
+    }
+    if state.alternate_scroll {
+        w.queue(commands::DisableAlternateScroll)?;
+        state.alternate_scroll = false;
+    }
+    if state.pointer_shape {
+        w.queue(commands::PopPointerShape)?;
+        state.pointer_shape = false;
+    }
+    if state.kitty_images {
+        w.queue(kitty_graphics::DeleteImages::All)?;
+        state.kitty_images = false;
+    }
+    Ok(())
+}
+
+/// Puts the terminal back into a usable state after the application crashed, possibly in the
+/// middle of a frame. Everything our commands can enable is disabled, whether or not it is known
+/// to be enabled, and raw mode is disabled at the end.
+///
+/// The order matters: input modes are turned off first so the terminal stops sending reports,
+/// then the screen state is reset while still on the alternate screen, which is left last.
+pub fn recover<W: Write>(w: &mut W) -> io::Result<()> {
+    w.queue(pixel_mouse::DisablePixelMouseReporting)?;
+    get().pixel_mouse = false;
+    w.queue(event::DisableMouseCapture)?;
+    w.queue(event::DisableFocusChange)?;
+    w.queue(event::DisableBracketedPaste)?;
+    let depth = std::mem::take(&mut get().keyboard_enhancement_depth);
+    if depth > 0 {
+        w.queue(commands::PopKeyboardEnhancementFlagsN(depth))?;
+    }
+    restore(w)?;
+    w.queue(commands::ResetScrollRegion)?;
+    w.queue(style::SetAttribute(style::Attribute::Reset))?;
+    w.queue(style::ResetColor)?;
+    w.queue(cursor::SetCursorStyle::DefaultUserShape)?;
+    w.queue(cursor::Show)?;
+    w.queue(terminal::EnableLineWrap)?;
+    w.queue(terminal::LeaveAlternateScreen)?;
+    w.flush()?;
+    terminal::disable_raw_mode()
+}
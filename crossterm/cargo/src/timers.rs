@@ -0,0 +1,138 @@
+//! One-shot and repeating timers, delivered by [`crate::event_source`] together with input.
+//!
+//! Repeating timers are scheduled from when they were started rather than from when a tick was
+//! read, so they do not drift. Ticks which were missed because nobody read events in time are
+//! skipped, not delivered in a burst.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Timer {
+    id: i64,
+    due: Instant,
+    period: Option<Duration>,
+}
+
+struct Timers {
+    next_id: i64,
+    timers: Vec<Timer>,
+}
+
+static TIMERS: Mutex<Timers> = Mutex::new(Timers { next_id: 1, timers: Vec::new() });
+
+fn timers() -> std::sync::MutexGuard<'static, Timers> {
+    TIMERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Starts a timer which is due after `delay`, and then every `period` if there is one. Returns
+/// its id, which is never reused.
+pub fn start(delay: Duration, period: Option<Duration>) -> i64 {
+    timers().start(Instant::now(), delay, period)
+}
+
+/// Cancels a timer, so it is not delivered again even if it is already due. Returns whether the
+/// timer was still active.
+pub fn cancel(id: i64) -> bool {
+    let mut timers = timers();
+    let before = timers.timers.len();
+    timers.timers.retain(|timer| timer.id != id);
+    timers.timers.len() != before
+}
+
+/// When the next timer is due, if any.
+pub fn next_due() -> Option<Instant> {
+    timers().timers.iter().map(|timer| timer.due).min()
+}
+
+/// Takes the timer which has been due for the longest, if any. A one-shot timer is removed, and
+/// a repeating timer is rescheduled to the next tick after `now` on its original schedule.
+pub fn take_due(now: Instant) -> Option<i64> {
+    timers().take_due(now)
+}
+
+impl Timers {
+    fn start(&mut self, now: Instant, delay: Duration, period: Option<Duration>) -> i64 {
+        // a delay too large for an `Instant` will not be due in the lifetime of the process anyway
+        let due = now.checked_add(delay).unwrap_or(now + Duration::from_secs(100 * 365 * 24 * 60 * 60));
+        let id = self.next_id;
+        self.next_id += 1;
+        self.timers.push(Timer { id, due, period });
+        id
+    }
+
+    fn take_due(&mut self, now: Instant) -> Option<i64> {
+        let (index, _) = self.timers.iter().enumerate().filter(|(_, timer)| timer.due <= now).min_by_key(|(_, timer)| timer.due)?;
+        let timer = &mut self.timers[index];
+        let id = timer.id;
+        match timer.period {
+            Some(period) => {
+                // the first tick after `now`, which is `period` minus how far `now` is into a period
+                let into_period = (now - timer.due).as_nanos() % period.as_nanos();
+                timer.due = now + (period - Duration::from_nanos(into_period as u64));
+            }
+            None => {
+                self.timers.swap_remove(index);
+            }
+        }
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn no_timers() -> Timers {
+        Timers { next_id: 1, timers: Vec::new() }
+    }
+
+    #[test]
+    fn one_shot_is_taken_once() {
+        let t0 = Instant::now();
+        let mut timers = no_timers();
+        let id = timers.start(t0, ms(10), None);
+        assert_eq!(timers.take_due(t0 + ms(9)), None);
+        assert_eq!(timers.take_due(t0 + ms(10)), Some(id));
+        assert_eq!(timers.take_due(t0 + ms(100)), None);
+    }
+
+    #[test]
+    fn repeating_stays_on_its_schedule() {
+        let t0 = Instant::now();
+        let mut timers = no_timers();
+        let id = timers.start(t0, ms(10), Some(ms(10)));
+        // read late, but the next tick is still due on the original grid
+        assert_eq!(timers.take_due(t0 + ms(13)), Some(id));
+        assert_eq!(timers.timers[0].due, t0 + ms(20));
+        assert_eq!(timers.take_due(t0 + ms(19)), None);
+        assert_eq!(timers.take_due(t0 + ms(20)), Some(id));
+        assert_eq!(timers.timers[0].due, t0 + ms(30));
+    }
+
+    #[test]
+    fn missed_ticks_are_skipped() {
+        let t0 = Instant::now();
+        let mut timers = no_timers();
+        let id = timers.start(t0, ms(10), Some(ms(10)));
+        // ticks at 10, 20, 30 and 40 were missed, they are delivered once and not in a burst
+        assert_eq!(timers.take_due(t0 + ms(45)), Some(id));
+        assert_eq!(timers.take_due(t0 + ms(45)), None);
+        assert_eq!(timers.timers[0].due, t0 + ms(50));
+    }
+
+    #[test]
+    fn longest_due_is_taken_first() {
+        let t0 = Instant::now();
+        let mut timers = no_timers();
+        let later = timers.start(t0, ms(20), None);
+        let earlier = timers.start(t0, ms(10), Some(ms(100)));
+        assert_eq!(timers.take_due(t0 + ms(30)), Some(earlier));
+        assert_eq!(timers.take_due(t0 + ms(30)), Some(later));
+        assert_eq!(timers.take_due(t0 + ms(30)), None);
+        assert_ne!(earlier, later);
+    }
+}
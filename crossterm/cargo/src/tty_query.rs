@@ -0,0 +1,273 @@
+//! Querying the terminal for information it reports back on its input stream.
+//!
+//! The reply is read directly from the controlling terminal, bypassing crossterm's event reader.
+//! Other threads are kept from reading input in the meantime, and keys typed while waiting for
+//! the reply are handed back to [`crate::event_source`], so they are not lost. Raw mode should be
+//! enabled, otherwise the reply is line buffered and echoed.
+
+use std::io;
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::Chars;
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+/// Writes `query` to the terminal and reads until `find_reply` finds a complete reply in what has
+/// been read so far, returning the range it covers. Returns the reply, or `None` if the terminal
+/// did not answer within `timeout`.
+#[cfg(unix)]
+pub fn query(
+    query: &str,
+    timeout: Duration,
+    find_reply: impl Fn(&[u8]) -> Option<Range<usize>>,
+) -> io::Result<Option<Vec<u8>>> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    crate::event_source::with_exclusive_input(|| {
+        let mut tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        tty.write_all(query.as_bytes())?;
+        tty.flush()?;
+
+        let deadline = Instant::now() + timeout;
+        let mut input = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let mut fds = libc::pollfd { fd: tty.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            let millis = remaining.as_millis().min(i32::MAX as u128) as i32;
+            let ready = unsafe { libc::poll(&mut fds, 1, millis) };
+            if ready < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            let n = if ready == 0 { 0 } else { tty.read(&mut buf)? };
+            if n == 0 {
+                return Ok((None, key_events(&input)));
+            }
+            input.extend_from_slice(&buf[..n]);
+            if let Some(range) = find_reply(&input) {
+                let reply = input[range.clone()].to_vec();
+                input.drain(range);
+                return Ok((Some(reply), key_events(&input)));
+            }
+        }
+    })
+}
+
+#[cfg(windows)]
+pub fn query(
+    _query: &str,
+    _timeout: Duration,
+    _find_reply: impl Fn(&[u8]) -> Option<Range<usize>>,
+) -> io::Result<Option<Vec<u8>>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "Querying the terminal is not implemented on Windows."))
+}
+
+/// Finds the first complete OSC reply starting with `prefix` (for instance `b"52;"`), from its
+/// introducer up to and including its terminator, which is either BEL or ST.
+pub fn osc_reply_range(input: &[u8], prefix: &[u8]) -> Option<Range<usize>> {
+    let marker = [b"\x1b]".as_slice(), prefix].concat();
+    let start = input.windows(marker.len()).position(|w| w == marker.as_slice())?;
+    let rest = &input[start + marker.len()..];
+    let end = (0..rest.len()).find_map(|i| match rest[i] {
+        0x07 => Some(i + 1),
+        _ if rest[i..].starts_with(b"\x1b\\") => Some(i + 2),
+        _ => None,
+    })?;
+    Some(start..start + marker.len() + end)
+}
+
+/// Finds the payload of the first OSC reply starting with `prefix`, see [`osc_reply_range`].
+pub fn osc_reply<'a>(reply: &'a [u8], prefix: &[u8]) -> Option<&'a [u8]> {
+    let range = osc_reply_range(reply, prefix)?;
+    let sequence = &reply[range.start + 2 + prefix.len()..range.end];
+    sequence.strip_suffix(b"\x07").or_else(|| sequence.strip_suffix(b"\x1b\\"))
+}
+
+/// Turns input which was read while waiting for a reply back into key events. Only a few keys
+/// are typed during a query, so this knows characters, control keys and the common cursor and
+/// editing keys, and drops any other sequence.
+pub fn key_events(input: &[u8]) -> Vec<Event> {
+    let text = String::from_utf8_lossy(input);
+    let mut chars = text.chars().peekable();
+    let mut events = Vec::new();
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' => match chars.next() {
+                None => Some(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+                Some('[') => csi_key(&mut chars),
+                Some('O') => chars.next().and_then(ss3_key),
+                Some(c) => char_key(c).map(|key| KeyEvent::new(key.code, key.modifiers | KeyModifiers::ALT)),
+            },
+            c => char_key(c),
+        };
+        events.extend(key.map(Event::Key));
+    }
+    events
+}
+
+/// A key which is sent as a single character, mapped the way crossterm maps it.
+fn char_key(c: char) -> Option<KeyEvent> {
+    let (code, modifiers) = match c {
+        '\r' | '\n' => (KeyCode::Enter, KeyModifiers::NONE),
+        '\t' => (KeyCode::Tab, KeyModifiers::NONE),
+        '\x7f' => (KeyCode::Backspace, KeyModifiers::NONE),
+        '\0' => (KeyCode::Char(' '), KeyModifiers::CONTROL),
+        '\x01'..='\x1a' => (KeyCode::Char(char::from(c as u8 - 0x01 + b'a')), KeyModifiers::CONTROL),
+        '\x1c'..='\x1f' => (KeyCode::Char(char::from(c as u8 - 0x1c + b'4')), KeyModifiers::CONTROL),
+        c if c.is_control() => return None,
+        c if c.is_uppercase() => (KeyCode::Char(c), KeyModifiers::SHIFT),
+        c => (KeyCode::Char(c), KeyModifiers::NONE),
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// A key sent as `CSI params final`, where the second parameter holds the modifiers.
+fn csi_key(chars: &mut Peekable<Chars>) -> Option<KeyEvent> {
+    let mut params = String::new();
+    while let Some(c) = chars.next_if(|c| ('\x30'..='\x3f').contains(c)) {
+        params.push(c);
+    }
+    let last = chars.next_if(|c| ('\x40'..='\x7e').contains(c))?;
+    let mut parts = params.split(';');
+    let number = parts.next().unwrap_or_default();
+    let modifiers = match parts.next().map(str::parse::<u8>) {
+        None => KeyModifiers::NONE,
+        Some(Ok(mask)) if mask > 0 => modifiers(mask - 1),
+        Some(_) => return None,
+    };
+    let code = match (last, number) {
+        ('A', _) => KeyCode::Up,
+        ('B', _) => KeyCode::Down,
+        ('C', _) => KeyCode::Right,
+        ('D', _) => KeyCode::Left,
+        ('H', _) | ('~', "1" | "7") => KeyCode::Home,
+        ('F', _) | ('~', "4" | "8") => KeyCode::End,
+        ('Z', _) => return Some(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        ('~', "2") => KeyCode::Insert,
+        ('~', "3") => KeyCode::Delete,
+        ('~', "5") => KeyCode::PageUp,
+        ('~', "6") => KeyCode::PageDown,
+        ('~', "15") => KeyCode::F(5),
+        ('~', "17") => KeyCode::F(6),
+        ('~', "18") => KeyCode::F(7),
+        ('~', "19") => KeyCode::F(8),
+        ('~', "20") => KeyCode::F(9),
+        ('~', "21") => KeyCode::F(10),
+        ('~', "23") => KeyCode::F(11),
+        ('~', "24") => KeyCode::F(12),
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// A key sent as `ESC O final`, which is how some terminals send the cursor keys and F1 to F4.
+fn ss3_key(last: char) -> Option<KeyEvent> {
+    let code = match last {
+        'A' => KeyCode::Up,
+        'B' => KeyCode::Down,
+        'C' => KeyCode::Right,
+        'D' => KeyCode::Left,
+        'H' => KeyCode::Home,
+        'F' => KeyCode::End,
+        'P'..='S' => KeyCode::F(last as u8 - b'P' + 1),
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// Decodes the xterm modifier bits: 1 for shift, 2 for alt and 4 for control.
+fn modifiers(mask: u8) -> KeyModifiers {
+    let mut modifiers = KeyModifiers::NONE;
+    if mask & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if mask & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if mask & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn osc_reply_range_covers_introducer_and_terminator() {
+        let input = b"ab\x1b]52;c;aGk=\x07cd";
+        assert_eq!(osc_reply_range(input, b"52;"), Some(2..14));
+        assert_eq!(osc_reply(input, b"52;"), Some(b"c;aGk=".as_slice()));
+
+        let input = b"\x1b]52;c;aGk=\x1b\\x";
+        assert_eq!(osc_reply_range(input, b"52;"), Some(0..13));
+        assert_eq!(osc_reply(input, b"52;"), Some(b"c;aGk=".as_slice()));
+    }
+
+    #[test]
+    fn osc_reply_range_waits_for_the_terminator() {
+        assert_eq!(osc_reply_range(b"\x1b]52;c;aGk", b"52;"), None);
+        assert_eq!(osc_reply_range(b"\x1b]11;rgb:0/0/0\x07", b"52;"), None);
+    }
+
+    #[test]
+    fn key_events_for_characters() {
+        assert_eq!(
+            key_events("aB€".as_bytes()),
+            vec![
+                key(KeyCode::Char('a'), KeyModifiers::NONE),
+                key(KeyCode::Char('B'), KeyModifiers::SHIFT),
+                key(KeyCode::Char('€'), KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_events_for_control_keys() {
+        assert_eq!(
+            key_events(b"\r\t\x7f\x03\x1b"),
+            vec![
+                key(KeyCode::Enter, KeyModifiers::NONE),
+                key(KeyCode::Tab, KeyModifiers::NONE),
+                key(KeyCode::Backspace, KeyModifiers::NONE),
+                key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                key(KeyCode::Esc, KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_events_for_escape_sequences() {
+        assert_eq!(
+            key_events(b"\x1b[A\x1bOB\x1b[1;5C\x1b[3~\x1b[6;2~\x1bOP\x1b[Z\x1bx"),
+            vec![
+                key(KeyCode::Up, KeyModifiers::NONE),
+                key(KeyCode::Down, KeyModifiers::NONE),
+                key(KeyCode::Right, KeyModifiers::CONTROL),
+                key(KeyCode::Delete, KeyModifiers::NONE),
+                key(KeyCode::PageDown, KeyModifiers::SHIFT),
+                key(KeyCode::F(1), KeyModifiers::NONE),
+                key(KeyCode::BackTab, KeyModifiers::SHIFT),
+                key(KeyCode::Char('x'), KeyModifiers::ALT),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_events_drop_unknown_sequences() {
+        assert_eq!(key_events(b"\x1b[<0;1;1Mx"), vec![key(KeyCode::Char('x'), KeyModifiers::NONE)]);
+    }
+}
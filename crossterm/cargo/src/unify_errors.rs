@@ -6,6 +6,8 @@ pub enum UnifiedError {
     Jni(JniError),
     Io(io::Error),
     NotU16(jint),
+    InvalidArgument(String),
+    IllegalState(String),
 }
 
 pub type UnifiedResult<T> = Result<T, UnifiedError>;
@@ -0,0 +1,13 @@
+package tui.crossterm;
+
+/// Which selection an OSC 52 clipboard command reads or writes.
+///
+/// Terminals generally only implement [#Clipboard]; X11-based ones may also honour the others.
+public enum ClipboardSelection {
+  /// The system clipboard.
+  Clipboard,
+  /// The primary selection, which on X11 holds the currently selected text.
+  Primary,
+  /// The secondary selection.
+  Secondary
+}
@@ -2,6 +2,7 @@ package tui.crossterm;
 
 import java.util.List;
 import java.util.Optional;
+import java.util.OptionalInt;
 
 public sealed interface Command
     permits Command.MoveTo,
@@ -23,6 +24,10 @@ public sealed interface Command
         Command.SetCursorStyle,
         Command.EnableMouseCapture,
         Command.DisableMouseCapture,
+        Command.EnablePixelMouseReporting,
+        Command.DisablePixelMouseReporting,
+        Command.EnableAlternateScroll,
+        Command.DisableAlternateScroll,
         Command.PushKeyboardEnhancementFlags,
         Command.PopKeyboardEnhancementFlags,
         Command.EnableFocusChange,
@@ -43,9 +48,56 @@ public sealed interface Command
         Command.LeaveAlternateScreen,
         Command.ScrollUp,
         Command.ScrollDown,
+        Command.SetScrollRegion,
+        Command.ResetScrollRegion,
+        Command.ScrollUpInRegion,
+        Command.ScrollDownInRegion,
+        Command.InsertLines,
+        Command.DeleteLines,
+        Command.InsertChars,
+        Command.DeleteChars,
+        Command.EraseChars,
         Command.Clear,
         Command.SetSize,
-        Command.Print {
+        Command.DeiconifyWindow,
+        Command.IconifyWindow,
+        Command.MoveWindow,
+        Command.ResizeWindowPixels,
+        Command.RaiseWindow,
+        Command.LowerWindow,
+        Command.MaximizeWindow,
+        Command.UnmaximizeWindow,
+        Command.EnterFullscreen,
+        Command.ExitFullscreen,
+        Command.ToggleFullscreen,
+        Command.SoftReset,
+        Command.FullReset,
+        Command.SetClipboard,
+        Command.Notify,
+        Command.SetProgress,
+        Command.ClearProgress,
+        Command.MarkPromptStart,
+        Command.MarkCommandStart,
+        Command.MarkOutputStart,
+        Command.MarkCommandFinished,
+        Command.SetWorkingDirectory,
+        Command.SetPaletteColor,
+        Command.ResetPaletteColor,
+        Command.ResetPalette,
+        Command.SetDynamicColor,
+        Command.ResetDynamicColor,
+        Command.SetPointerShape,
+        Command.ResetPointerShape,
+        Command.Print,
+        Command.PrintStyled,
+        Command.TransmitRgbaImage,
+        Command.TransmitPngImage,
+        Command.PlaceImage,
+        Command.DeleteImage,
+        Command.DeletePlacement,
+        Command.DeleteAllImages,
+        Command.DrawSixel,
+        Command.DrawInlineImage {
   /// A command that moves the terminal cursor to the given position (column, row).
   /// * Top left cell is represented as `0,0`.
   record MoveTo(int x, int y) implements Command {}
@@ -137,6 +189,31 @@ public sealed interface Command
   /// Mouse events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
   record DisableMouseCapture() implements Command {}
 
+  /// A command that makes mouse events report pixel positions (SGR-pixel mode, DEC mode 1016).
+  ///
+  /// Use it together with [`EnableMouseCapture`]. Mouse events then carry the position in
+  /// pixels in [MouseEvent#pixel()], while the column and row are derived from it using the size
+  /// of a cell in pixels, which is taken from the window size in pixels.
+  ///
+  /// * `cell_width` x `cell_height` is the cell size used when the terminal does not report its
+  ///   window size in pixels. It must not be empty. Get it from `CrosstermJni.cellSize()`.
+  /// * [`DisableMouseCapture`] also disables pixel reporting.
+  record EnablePixelMouseReporting(int cell_width, int cell_height) implements Command {}
+
+  /// A command that makes mouse events report cell positions again.
+  record DisablePixelMouseReporting() implements Command {}
+
+  /// A command that makes the terminal turn mouse wheel scrolls into Up/Down arrow key events
+  /// while on the alternate screen (alternate scroll mode, DEC mode 1007).
+  ///
+  /// This only has an effect while mouse capture is disabled, so it allows wheel scrolling
+  /// without taking over text selection. It is disabled by `CrosstermJni.restoreTerminalState()`
+  /// if still enabled.
+  record EnableAlternateScroll() implements Command {}
+
+  /// A command that disables alternate scroll mode.
+  record DisableAlternateScroll() implements Command {}
+
   /// A command that enables the [kitty keyboard
   // protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/), which adds extra information to
   // keyboard events and removes ambiguity for modifier keys.
@@ -239,11 +316,316 @@ public sealed interface Command
   /// A command that scrolls the terminal screen a given number of rows down.
   record ScrollDown(int num_rows) implements Command {}
 
+  /// A command that restricts scrolling to the rows between `top` and `bottom` (DECSTBM).
+  /// * Both rows are 0 based and inclusive.
+  /// * The region must span at least two rows, so `top` must be less than `bottom`.
+  /// * Use [`ResetScrollRegion`] to scroll the whole screen again.
+  record SetScrollRegion(int top, int bottom) implements Command {}
+
+  /// A command that resets the scrolling region to the whole screen.
+  record ResetScrollRegion() implements Command {}
+
+  /// A command that scrolls the rows between `first_row` and `last_row` up by `lines_to_scroll`
+  /// rows, leaving the rest of the screen untouched.
+  /// * Both rows are 0 based and inclusive.
+  /// * The scrolling region is reset afterwards, and the cursor position is undefined.
+  record ScrollUpInRegion(int first_row, int last_row, int lines_to_scroll) implements Command {}
+
+  /// A command that scrolls the rows between `first_row` and `last_row` down by `lines_to_scroll`
+  /// rows, leaving the rest of the screen untouched.
+  /// * Both rows are 0 based and inclusive.
+  /// * The scrolling region is reset afterwards, and the cursor position is undefined.
+  record ScrollDownInRegion(int first_row, int last_row, int lines_to_scroll)
+      implements Command {}
+
+  /// A command that inserts blank lines at the cursor row (IL), pushing the rows below it down.
+  /// * Lines pushed past the bottom of the scrolling region are lost.
+  /// * A count of 0 does nothing.
+  record InsertLines(int num_lines) implements Command {}
+
+  /// A command that deletes lines starting at the cursor row (DL), pulling the rows below it up.
+  /// * Blank lines are added at the bottom of the scrolling region.
+  /// * A count of 0 does nothing.
+  record DeleteLines(int num_lines) implements Command {}
+
+  /// A command that inserts blank characters at the cursor (ICH), shifting the rest of the row
+  /// to the right.
+  /// * Characters shifted past the right margin are lost.
+  /// * A count of 0 does nothing.
+  record InsertChars(int num_chars) implements Command {}
+
+  /// A command that deletes characters at the cursor (DCH), shifting the rest of the row to the
+  /// left.
+  /// * Blank characters are added at the right margin.
+  /// * A count of 0 does nothing.
+  record DeleteChars(int num_chars) implements Command {}
+
+  /// A command that erases characters starting at the cursor (ECH), without shifting the rest of
+  /// the row.
+  /// * A count of 0 does nothing.
+  record EraseChars(int num_chars) implements Command {}
+
   /// A command that clears the terminal screen buffer.
   record Clear(ClearType clear_type) implements Command {}
 
   /// A command that sets the terminal buffer size `(columns, rows)`.
   record SetSize(int columns, int rows) implements Command {}
 
+  /// A command that restores the terminal window if it is minimized (XTWINOPS 1).
+  ///
+  /// The window manipulation commands are ignored by many terminals, or only honoured when
+  /// enabled in their configuration.
+  record DeiconifyWindow() implements Command {}
+
+  /// A command that minimizes the terminal window (XTWINOPS 2).
+  record IconifyWindow() implements Command {}
+
+  /// A command that moves the top left corner of the terminal window to the given pixel position
+  /// on the screen (XTWINOPS 3).
+  record MoveWindow(int x, int y) implements Command {}
+
+  /// A command that resizes the text area of the terminal window to the given size in pixels
+  /// (XTWINOPS 4).
+  /// * A size of 0 keeps the current width or height.
+  /// * Use [`SetSize`] to resize in cells.
+  record ResizeWindowPixels(int width, int height) implements Command {}
+
+  /// A command that raises the terminal window to the front of the stacking order (XTWINOPS 5).
+  record RaiseWindow() implements Command {}
+
+  /// A command that lowers the terminal window to the bottom of the stacking order (XTWINOPS 6).
+  record LowerWindow() implements Command {}
+
+  /// A command that maximizes the terminal window (XTWINOPS 9).
+  record MaximizeWindow() implements Command {}
+
+  /// A command that restores a maximized terminal window to its previous size (XTWINOPS 9).
+  record UnmaximizeWindow() implements Command {}
+
+  /// A command that makes the terminal window fullscreen (XTWINOPS 10).
+  record EnterFullscreen() implements Command {}
+
+  /// A command that makes a fullscreen terminal window a normal window again (XTWINOPS 10).
+  record ExitFullscreen() implements Command {}
+
+  /// A command that toggles the terminal window between fullscreen and normal (XTWINOPS 10).
+  record ToggleFullscreen() implements Command {}
+
+  /// A command that performs a soft terminal reset (DECSTR).
+  ///
+  /// Resets the scrolling region, character sets, SGR attributes, cursor visibility and a few
+  /// other modes, but leaves the screen contents and the alternate screen alone.
+  record SoftReset() implements Command {}
+
+  /// A command that performs a full terminal reset (RIS), like the `reset` shell command.
+  ///
+  /// Resets everything, including the palette, and clears the screen and usually the scrollback.
+  /// Afterwards `CrosstermJni.restoreTerminalState()` has nothing left to undo. See `CrosstermJni.recoverTerminal()` for a gentler way to recover from a crash.
+  record FullReset() implements Command {}
+
+  /// A command that copies `text` to a clipboard selection using OSC 52.
+  ///
+  /// This goes through the terminal rather than a local clipboard API, so it also works over
+  /// SSH. The text is base64 encoded natively, and wrapped for tmux passthrough when running
+  /// inside tmux. Some terminals limit the size of the payload, or need to be configured to allow
+  /// clipboard access.
+  record SetClipboard(ClipboardSelection selection, String text) implements Command {}
+
+  /// A command that shows a desktop notification, for instance when a long-running job finishes
+  /// while the terminal is not focused (see [Event.FocusLost]).
+  ///
+  /// The escape sequence is picked based on the detected terminal: `OSC 9` for iTerm2, WezTerm
+  /// and ghostty, `OSC 777` for rxvt, foot and VTE-based terminals, and `OSC 99` for kitty. On
+  /// other terminals nothing is written.
+  ///
+  /// `id` and `urgency` are only used by kitty, where a notification replaces any previous one
+  /// with the same id. Ids may only contain ASCII letters, digits and `-_+.`.
+  record Notify(String title, String body, Optional<String> id, NotificationUrgency urgency)
+      implements Command {
+    public Notify(String title, String body) {
+      this(title, body, Optional.empty(), NotificationUrgency.Normal);
+    }
+  }
+
+  /// A command that reports progress in the terminal tab or taskbar, using `OSC 9 ; 4`.
+  ///
+  /// This is shown by Windows Terminal, ConEmu, ghostty and iTerm2. On other terminals nothing is
+  /// written, since some of them would show the sequence as an `OSC 9` notification instead.
+  ///
+  /// * `percent` must be between 0 and 100.
+  /// * The indicator is cleared by `CrosstermJni.restoreTerminalState()` if still shown.
+  record SetProgress(ProgressState state, int percent) implements Command {}
+
+  /// A command that removes the progress indicator set by [`SetProgress`].
+  record ClearProgress() implements Command {}
+
+  /// A command that marks the start of a prompt (`OSC 133 ; A`).
+  ///
+  /// Terminals with shell integration (WezTerm, kitty, iTerm2, VS Code, ...) use the `OSC 133`
+  /// marks to jump between prompts and to select the output of a single command. Inline viewports
+  /// printing results into the scrollback can emit them around each result.
+  record MarkPromptStart() implements Command {}
+
+  /// A command that marks the end of a prompt and the start of the command input
+  /// (`OSC 133 ; B`).
+  record MarkCommandStart() implements Command {}
+
+  /// A command that marks the start of the command output (`OSC 133 ; C`).
+  record MarkOutputStart() implements Command {}
+
+  /// A command that marks the end of the command output (`OSC 133 ; D`), optionally with the exit
+  /// code of the command.
+  record MarkCommandFinished(OptionalInt exit_code) implements Command {}
+
+  /// A command that reports the current working directory (`OSC 7`), which terminals use when
+  /// opening new tabs or windows.
+  ///
+  /// * `path` must be absolute.
+  /// * The hostname is looked up and the path is percent-encoded natively.
+  record SetWorkingDirectory(String path) implements Command {}
+
+  /// A command that redefines one of the 256 indexed palette colors (`OSC 4`), which changes the
+  /// color of all content using [Color.AnsiValue] `index`, or the named color it corresponds to.
+  ///
+  /// * `index` must be between 0 and 255.
+  /// * The terminal remembers the change, so it is undone by
+  ///   `CrosstermJni.restoreTerminalState()` unless reset before that.
+  record SetPaletteColor(int index, Color.Rgb color) implements Command {}
+
+  /// A command that resets one indexed palette color to the terminal default (`OSC 104`).
+  record ResetPaletteColor(int index) implements Command {}
+
+  /// A command that resets all indexed palette colors to the terminal defaults (`OSC 104`).
+  record ResetPalette() implements Command {}
+
+  /// A command that redefines the default foreground, default background or cursor color
+  /// (`OSC 10`, `11` and `12`).
+  ///
+  /// * The terminal remembers the change, so it is undone by
+  ///   `CrosstermJni.restoreTerminalState()` unless reset before that.
+  record SetDynamicColor(DynamicColor which, Color.Rgb color) implements Command {}
+
+  /// A command that resets the default foreground, default background or cursor color to the
+  /// terminal default (`OSC 110`, `111` and `112`).
+  record ResetDynamicColor(DynamicColor which) implements Command {}
+
+  /// A command that changes the shape of the mouse pointer while it is over the terminal
+  /// (`OSC 22`), for instance to `pointer` over a button or `col-resize` over a table column
+  /// divider.
+  ///
+  /// The first shape is pushed onto the terminal's stack of pointer shapes (`OSC 22 ; > name`),
+  /// and later ones replace it, so the shape from before is kept underneath. Supported by kitty,
+  /// and by other terminals implementing its pointer shape stack, and ignored elsewhere.
+  ///
+  /// * `name` must be one of the CSS cursor names: `default`, `none`, `context-menu`, `help`,
+  ///   `pointer`, `progress`, `wait`, `cell`, `crosshair`, `text`, `vertical-text`, `alias`,
+  ///   `copy`, `move`, `no-drop`, `not-allowed`, `grab`, `grabbing`, `all-scroll`, `col-resize`,
+  ///   `row-resize`, `n-resize`, `e-resize`, `s-resize`, `w-resize`, `ne-resize`, `nw-resize`,
+  ///   `se-resize`, `sw-resize`, `ew-resize`, `ns-resize`, `nesw-resize`, `nwse-resize`,
+  ///   `zoom-in` or `zoom-out`.
+  /// * The original shape is restored by [`DisableMouseCapture`], and by
+  ///   `CrosstermJni.restoreTerminalState()`.
+  record SetPointerShape(String name) implements Command {}
+
+  /// A command that changes the mouse pointer back to the shape it had before the first
+  /// [`SetPointerShape`], by popping it off the terminal's stack (`OSC 22 ; <`). Does nothing if
+  /// no shape was set since the last reset.
+  record ResetPointerShape() implements Command {}
+
   record Print(String value) implements Command {}
+
+  /// A command that prints `text` in the given style.
+  ///
+  /// Mirrors `crossterm::style::PrintStyledContent`.
+  ///
+  /// The colors and attributes in the style are set before the text, which makes this handy for
+  /// one-off styled output like progress lines or prompts. Afterwards they are reset to the
+  /// terminal defaults rather than to what was in effect before:
+  ///
+  /// * if the style has any attributes, everything is reset (`SGR 0`), including colors and
+  ///   attributes which were set by earlier commands;
+  /// * otherwise the background is reset to the default if the style sets it, and the foreground
+  ///   if the style sets the foreground or the underline color.
+  record PrintStyled(String text, ContentStyle style) implements Command {}
+
+  /// A command that transmits an image in RGBA format through the kitty graphics protocol, and
+  /// stores it in the terminal under `image_id`, replacing an earlier image with the same id.
+  ///
+  /// * `image_id` must be positive.
+  /// * `pixels` holds four bytes per pixel, row by row, so it must be exactly
+  ///   `width * height * 4` bytes long.
+  /// * Nothing is shown until the image is placed with [`PlaceImage`].
+  ///
+  /// The data is base64 encoded and split into chunks natively. Terminals without support for the
+  /// protocol ignore it. Transmitted images are deleted by `CrosstermJni.restoreTerminalState()`.
+  record TransmitRgbaImage(int image_id, int width, int height, byte[] pixels)
+      implements Command {}
+
+  /// A command that transmits a PNG file through the kitty graphics protocol. The terminal
+  /// decodes it, otherwise this works like [`TransmitRgbaImage`].
+  record TransmitPngImage(int image_id, byte[] png) implements Command {}
+
+  /// A command that shows a transmitted image, scaled to fill `columns` x `rows` cells with the
+  /// top left corner at the given cell (column, row).
+  ///
+  /// * `placement_id` must be positive. Placing the same image with the same placement id again
+  ///   moves the existing placement instead of adding another one.
+  /// * Placements with a higher `z_index` are drawn on top. A negative `z_index` draws the image
+  ///   below the text.
+  /// * The cursor is moved to the top left corner of the placement.
+  record PlaceImage(
+      int image_id, int placement_id, int x, int y, int columns, int rows, int z_index)
+      implements Command {}
+
+  /// A command that removes all placements of an image, and frees its data in the terminal.
+  record DeleteImage(int image_id) implements Command {}
+
+  /// A command that removes a single placement of an image. The image stays transmitted, so it
+  /// can be placed again.
+  record DeletePlacement(int image_id, int placement_id) implements Command {}
+
+  /// A command that removes all placements of all images, and frees their data in the terminal.
+  record DeleteAllImages() implements Command {}
+
+  /// A command that draws an RGBA image as sixel graphics, for terminals which support sixel but
+  /// not the kitty graphics protocol, like xterm, foot, mlterm and WezTerm.
+  ///
+  /// * The image is scaled to fit `columns` x `rows` cells with the top left corner at the given
+  ///   cell (column, row), keeping its aspect ratio.
+  /// * `cell_width` x `cell_height` is the size of a cell in pixels, which must not be empty. Get
+  ///   it from `CrosstermJni.cellSize()`.
+  /// * `pixels` holds four bytes per pixel, row by row, so it must be exactly
+  ///   `width * height * 4` bytes long. Pixels which are mostly transparent are not drawn.
+  /// * The image is reduced to at most 256 colors natively, with dithering.
+  /// * The cursor position is undefined afterwards.
+  ///
+  /// Unlike kitty images, sixel images are part of the cell contents, so they are overwritten by
+  /// whatever is drawn in those cells later.
+  record DrawSixel(
+      int x,
+      int y,
+      int columns,
+      int rows,
+      int cell_width,
+      int cell_height,
+      int width,
+      int height,
+      byte[] pixels)
+      implements Command {}
+
+  /// A command that shows an image through the iTerm2 inline image protocol (`OSC 1337 ; File=`),
+  /// which iTerm2 and WezTerm understand.
+  ///
+  /// * `data` is an image file in any format the terminal can decode, like PNG, JPEG or GIF. It
+  ///   must not be empty, and at most 16 MiB.
+  /// * The image is scaled to `columns` x `rows` cells with the top left corner at the given cell
+  ///   (column, row). With `preserve_aspect_ratio` the image is fit inside those cells instead of
+  ///   being stretched to fill them.
+  /// * The cursor position is undefined afterwards.
+  ///
+  /// Nothing is written if the terminal is not known to support the protocol, check
+  /// `CrosstermJni.supportsInlineImages()` to fall back to [`DrawSixel`] or text instead.
+  record DrawInlineImage(
+      int x, int y, int columns, int rows, byte[] data, boolean preserve_aspect_ratio)
+      implements Command {}
 }
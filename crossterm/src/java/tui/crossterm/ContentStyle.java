@@ -0,0 +1,24 @@
+package tui.crossterm;
+
+import java.util.List;
+import java.util.Optional;
+
+/// The style of a piece of content: colors and attributes.
+///
+/// Mirrors `crossterm::style::ContentStyle`.
+public record ContentStyle(
+    /// The foreground color.
+    Optional<Color> foreground_color,
+    /// The background color.
+    Optional<Color> background_color,
+    /// The underline color.
+    Optional<Color> underline_color,
+    /// The attributes, like bold or italic.
+    Attributes attributes) {
+
+  /// Returns a style without colors or attributes, which leaves the terminal style alone.
+  public static ContentStyle empty() {
+    return new ContentStyle(
+        Optional.empty(), Optional.empty(), Optional.empty(), new Attributes(List.of()));
+  }
+}
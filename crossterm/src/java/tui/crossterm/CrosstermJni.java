@@ -2,6 +2,9 @@ package tui.crossterm;
 
 import java.util.Arrays;
 import java.util.List;
+import java.util.Optional;
+import java.util.concurrent.BlockingQueue;
+import java.util.function.Consumer;
 
 public class CrosstermJni {
   static {
@@ -20,6 +23,94 @@ public class CrosstermJni {
 
   public native Event read();
 
+  /// Waits up to `timeout` for the first event, then returns it together with all events which
+  /// are already available, without waiting for more. At most `maxEvents` events are returned.
+  /// The list ends at the first [Event.Readable], since registered file descriptors stay readable
+  /// until they are read.
+  ///
+  /// This replaces a `poll` + `read` round trip per event when events come in bursts, like
+  /// resizes, mouse moves or a paste without bracketed paste. Returns an empty list if no event
+  /// arrived within the timeout.
+  public native List<Event> readAll(int maxEvents, Duration timeout);
+
+  /// Wakes up a thread blocked in [#poll(Duration)] or [#read()], or the next one to call them,
+  /// so a background job can have the UI redraw without waiting for the poll timeout. `poll`
+  /// returns `true` and the next read returns [Event.Woken]. May be called from any thread.
+  public native void wake();
+
+  /// Turns coalescing of redundant events on or off, it is off by default. When on, consecutive
+  /// mouse moves, or drags with the same button, which have the same modifiers are merged into
+  /// the latest one, and so are consecutive resizes. Only events which arrived before they were read
+  /// are merged, so nothing is delayed. [Event.Mouse#merged()] and [Event.Resize#merged()] tell
+  /// how many events were dropped, and together with [Event#timestamp()] that still allows to
+  /// compute the velocity of a drag.
+  public native void setCoalescing(boolean enabled);
+
+  /// The current time in nanoseconds on the monotonic clock used for [Event#timestamp()], for
+  /// instance to measure the latency from input to render. It only makes sense to compare it to
+  /// other values from this clock, it is not related to [System#nanoTime()].
+  public native long now();
+
+  /// Makes [#poll(Duration)] and [#read()] also wait on the file descriptor `fd`, for instance a
+  /// socket, a pipe from a child process or an inotify descriptor. When it is ready to be read,
+  /// `poll` returns `true` and `read` returns [Event.Readable] with every registered descriptor
+  /// which is ready. Terminal events are returned first. Registering a descriptor twice does
+  /// nothing.
+  ///
+  /// Unregister the descriptor before closing it. Not supported on Windows.
+  public native void registerFd(int fd);
+
+  /// Stops waiting on a file descriptor registered with [#registerFd(int)]. Does nothing if it
+  /// was not registered.
+  public native void unregisterFd(int fd);
+
+  /// Starts a timer which is delivered once as [Event.Timer] through [#read()] after `delay`, and
+  /// returns its id. Timers wake up a blocked [#poll(Duration)] like any other event, so there is
+  /// no need to compute poll timeouts for animations or auto dismissing messages.
+  public native long startTimer(Duration delay);
+
+  /// Starts a timer which is delivered as [Event.Timer] every `period`, and returns its id. Ticks
+  /// follow the schedule from when the timer was started, so they do not drift. Ticks which were
+  /// missed because events were not read in time are skipped rather than delivered in a burst.
+  /// Throws [IllegalArgumentException] if `period` is zero.
+  public native long startRepeatingTimer(Duration period);
+
+  /// Cancels a timer, so it is not delivered again even if it is already due. Returns whether the
+  /// timer was still active.
+  public native boolean cancelTimer(long id);
+
+  /// Starts a native thread which reads events and passes each one to `consumer`, on that thread,
+  /// until [#stopEventReader()] is called. Exceptions thrown by the consumer are printed and do
+  /// not stop the reader. If reading events fails, the consumer receives [Event.ReaderFailed]
+  /// and the reader stops.
+  ///
+  /// Do not call [#poll(Duration)], [#read()] or [#readAll(int, Duration)] while the reader runs,
+  /// they would compete for the same events. Throws [IllegalStateException] if a reader is
+  /// already running.
+  public native void startEventReader(Consumer<Event> consumer);
+
+  /// Starts the event reader, see [#startEventReader(Consumer)], putting every event on `queue`.
+  /// The reader blocks while a bounded queue is full, until [#stopEventReader()] interrupts it.
+  public final void startEventReader(BlockingQueue<Event> queue) {
+    startEventReader(
+        (Consumer<Event>)
+            event -> {
+              try {
+                queue.put(event);
+              } catch (InterruptedException e) {
+                Thread.currentThread().interrupt();
+              }
+            });
+  }
+
+  /// Stops the event reader and waits for its thread to finish. When called from the consumer
+  /// the thread finishes after the consumer returns, without waiting. Does nothing if no reader
+  /// is running.
+  ///
+  /// The reader thread is interrupted, so a consumer which is blocked, for instance putting an
+  /// event on a full queue, gives up instead of keeping the reader from finishing.
+  public native void stopEventReader();
+
   public native Xy terminalSize();
 
   public native Xy cursorPosition();
@@ -28,6 +119,62 @@ public class CrosstermJni {
 
   public native void enableRawMode();
 
+  private native String readClipboard0(ClipboardSelection selection, Duration timeout);
+
+  /// Asks the terminal for the contents of a clipboard selection using OSC 52.
+  ///
+  /// Raw mode should be enabled. Reading events on other threads, including the event reader,
+  /// pauses while this waits for the reply, and keys typed in the meantime are delivered as
+  /// events afterwards. Returns empty if the terminal did not answer within `timeout`, which is
+  /// what happens when it does not support reading the clipboard or has it disabled.
+  public final Optional<String> readClipboard(ClipboardSelection selection, Duration timeout) {
+    return Optional.ofNullable(readClipboard0(selection, timeout));
+  }
+
+  /// Undoes changes made through commands which the terminal would otherwise keep after the
+  /// application exits: a progress indicator set by [Command.SetProgress], colors redefined by
+  /// [Command.SetPaletteColor] and [Command.SetDynamicColor], alternate scroll mode enabled by
+  /// [Command.EnableAlternateScroll], the pointer shape set by [Command.SetPointerShape], and
+  /// images transmitted by [Command.TransmitRgbaImage] and [Command.TransmitPngImage].
+  ///
+  /// Only changes which are still in effect are undone, so this is cheap to call unconditionally
+  /// when shutting down.
+  public native void restoreTerminalState();
+
+  /// Puts the terminal back into a usable state after the application crashed, possibly in the
+  /// middle of a frame.
+  ///
+  /// Disables mouse capture, focus change events and bracketed paste, pops all pushed keyboard
+  /// enhancement flags, undoes tracked terminal state (see [#restoreTerminalState()]), resets the
+  /// scrolling region, colors and attributes and the cursor style, shows the cursor, leaves the
+  /// alternate screen and finally disables raw mode. The output is flushed.
+  public native void recoverTerminal();
+
+  /// Whether the terminal is known to show images sent with [Command.DrawInlineImage]. The
+  /// terminal is detected from environment variables, so this does not block.
+  public native boolean supportsInlineImages();
+
+  /// The size of a cell in pixels, as `x` (width) and `y` (height), for [Command.DrawSixel] and
+  /// [Command.EnablePixelMouseReporting].
+  ///
+  /// Taken from the window size in pixels if the terminal reports it, otherwise the terminal is
+  /// asked, which blocks for up to 100ms if it does not answer. A guess is returned in that case.
+  /// Call this once up front and again after the terminal was resized, not for every frame.
+  public native Xy cellSize();
+
+  /// Decodes a PNG, JPEG or GIF file. Only the first frame of an animated GIF is decoded.
+  ///
+  /// Throws [IllegalArgumentException] if the data can not be decoded.
+  public native RgbaImage decodeImage(byte[] data);
+
+  /// Scales an image to fit `columns` x `rows` cells for drawing with `▀` half blocks, keeping
+  /// its aspect ratio. Each cell holds two pixels on top of each other.
+  ///
+  /// Returns two ARGB colors per cell, for the top and bottom half, with the cells row by row.
+  /// Cells which the image does not cover are fully transparent. Decode the image once and call
+  /// this for every frame, it is fast enough for animating small sprites.
+  public native int[] halfBlocks(RgbaImage image, int columns, int rows);
+
   public native void enqueue(List<Command> commands);
 
   public final void enqueue(Command... commands) {
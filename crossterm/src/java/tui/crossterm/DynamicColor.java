@@ -0,0 +1,11 @@
+package tui.crossterm;
+
+/// The colors besides the 256 indexed ones which a terminal lets applications redefine.
+public enum DynamicColor {
+  /// The default foreground color (`OSC 10`), used by [Color.Reset] text.
+  Foreground,
+  /// The default background color (`OSC 11`), used by [Color.Reset] cells.
+  Background,
+  /// The text cursor color (`OSC 12`).
+  Cursor
+}
@@ -1,24 +1,63 @@
 package tui.crossterm;
 
 public sealed interface Event
-    permits Event.FocusGained, Event.FocusLost, Event.Key, Event.Mouse, Event.Paste, Event.Resize {
+    permits Event.FocusGained,
+        Event.FocusLost,
+        Event.Key,
+        Event.Mouse,
+        Event.Paste,
+        Event.Readable,
+        Event.ReaderFailed,
+        Event.Resize,
+        Event.Timer,
+        Event.Woken {
+  /// When the event was parsed, or noticed for events which do not come from the terminal, in
+  /// nanoseconds on the same monotonic clock as [CrosstermJni#now()].
+  ///
+  /// Input is only parsed during [CrosstermJni#poll(Duration)], [CrosstermJni#read()] and
+  /// [CrosstermJni#readAll(int, Duration)], or continuously while the event reader runs. With the
+  /// event reader, or while a thread is blocked polling, this is when the input arrived. Input
+  /// which arrived while nobody was polling is stamped with the time of the next poll.
+  long timestamp();
+
   /// The terminal gained focus
-  record FocusGained() implements Event {}
+  record FocusGained(long timestamp) implements Event {}
 
   /// The terminal lost focus
-  record FocusLost() implements Event {}
+  record FocusLost(long timestamp) implements Event {}
 
   /// A single key event with additional pressed modifiers.
-  record Key(KeyEvent keyEvent) implements Event {}
+  record Key(KeyEvent keyEvent, long timestamp) implements Event {}
 
-  /// A single mouse event with additional pressed modifiers.
-  record Mouse(MouseEvent mouseEvent) implements Event {}
+  /// A single mouse event with additional pressed modifiers. `merged` counts the earlier moves or
+  /// drags which were coalesced into this one, see [CrosstermJni#setCoalescing(boolean)].
+  record Mouse(MouseEvent mouseEvent, int merged, long timestamp) implements Event {}
 
   /// A string that was pasted into the terminal. Only emitted if bracketed paste has been
   /// enabled.
-  record Paste(String string) implements Event {}
+  record Paste(String string, long timestamp) implements Event {}
+
+  /// Reading events failed in the event reader started with
+  /// [CrosstermJni#startEventReader(java.util.function.Consumer)], for instance because the
+  /// terminal went away. This is the last event its consumer receives, the reader has stopped.
+  record ReaderFailed(String message, long timestamp) implements Event {}
 
   /// An resize event with new dimensions after resize (columns, rows).
-  /// **Note** that resize events can occur in batches.
-  record Resize(int columns, int rows) implements Event {}
+  /// **Note** that resize events can occur in batches, unless they are coalesced, see
+  /// [CrosstermJni#setCoalescing(boolean)]. `merged` counts the earlier resizes which were
+  /// coalesced into this one.
+  record Resize(int columns, int rows, int merged, long timestamp) implements Event {}
+
+  /// File descriptors registered with [CrosstermJni#registerFd(int)] which are ready to be read,
+  /// or were closed or hung up on the other end. They are reported again as long as they stay
+  /// ready, so read them before polling again.
+  record Readable(int[] fds, long timestamp) implements Event {}
+
+  /// A timer started with [CrosstermJni#startTimer(Duration)] or
+  /// [CrosstermJni#startRepeatingTimer(Duration)] is due.
+  record Timer(long id, long timestamp) implements Event {}
+
+  /// Another thread called [CrosstermJni#wake()]. Any number of wake ups before the next read are
+  /// delivered as a single event.
+  record Woken(long timestamp) implements Event {}
 }
@@ -79,7 +79,10 @@ public sealed interface KeyCode
   /// A character.
   ///
   /// `KeyCode::Char('c')` represents `c` character, etc.
-  record Char(char c) implements KeyCode {}
+  ///
+  /// The character is a full Unicode code point, so characters outside the Basic Multilingual
+  /// Plane like emoji survive. Use `Character.toString(codePoint)` to turn it into a string.
+  record Char(int codePoint) implements KeyCode {}
 
   /// Null.
   record Null() implements KeyCode {}
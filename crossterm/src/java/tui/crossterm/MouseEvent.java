@@ -1,5 +1,7 @@
 package tui.crossterm;
 
+import java.util.Optional;
+
 public record MouseEvent(
     /// The kind of mouse event that was caused.
     MouseEventKind kind,
@@ -8,4 +10,12 @@ public record MouseEvent(
     /// The row that the event occurred on.
     int row,
     /// The key modifiers active when the event occurred.
-    KeyModifiers modifiers) {}
+    KeyModifiers modifiers,
+    /// The position in pixels that the event occurred on, relative to the top left corner of the
+    /// terminal. Only present while [Command.EnablePixelMouseReporting] is in effect.
+    Optional<Xy> pixel) {
+
+  public MouseEvent(MouseEventKind kind, int column, int row, KeyModifiers modifiers) {
+    this(kind, column, row, modifiers, Optional.empty());
+  }
+}
@@ -0,0 +1,8 @@
+package tui.crossterm;
+
+/// How urgent a [Command.Notify] notification is. Only kitty makes use of this.
+public enum NotificationUrgency {
+  Low,
+  Normal,
+  Critical
+}
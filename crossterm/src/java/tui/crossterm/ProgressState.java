@@ -0,0 +1,13 @@
+package tui.crossterm;
+
+/// The state of a [Command.SetProgress] indicator, which terminals usually show with a color.
+public enum ProgressState {
+  /// Progress is being made.
+  Normal,
+  /// The operation failed.
+  Error,
+  /// Progress is being made, but it is not known how far along it is. The percentage is ignored.
+  Indeterminate,
+  /// The operation is paused.
+  Paused
+}
@@ -0,0 +1,10 @@
+package tui.crossterm;
+
+/// A decoded image, see [CrosstermJni#decodeImage(byte[])].
+public record RgbaImage(
+    /// The width in pixels.
+    int width,
+    /// The height in pixels.
+    int height,
+    /// Four bytes per pixel (red, green, blue, alpha), row by row.
+    byte[] pixels) {}
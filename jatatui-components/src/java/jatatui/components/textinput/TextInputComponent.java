@@ -91,7 +91,7 @@ public final class TextInputComponent {
           }
           KeyCode.Char ch = (KeyCode.Char) e.code();
           int cursor = cursorState.get();
-          TextResult r = TextInput.insertAt(props.value(), cursor, ch.c());
+          TextResult r = TextInput.insertAt(props.value(), cursor, ch.codePoint());
           props.onChange().accept(r.value());
           cursorState.set(r.cursorPos());
           e.stopPropagation();
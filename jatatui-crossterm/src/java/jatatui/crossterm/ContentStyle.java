@@ -3,9 +3,12 @@ package jatatui.crossterm;
 import java.util.Optional;
 import tui.crossterm.Attributes;
 
-/// Mirror of crossterm's `ContentStyle` struct, kept inside `jatatui-crossterm` because the local
-/// JNI binding does not expose it. The `underline_color` field is included unconditionally;
-/// upstream gates it behind the `underline-color` cargo feature, but Java has no equivalent.
+/// Mirror of crossterm's `ContentStyle` struct, used as the source of the
+/// `FromCrossterm<ContentStyle> for Style` conversion in [CrosstermStyleConv]. The JNI binding's
+/// own [tui.crossterm.ContentStyle], accepted by [tui.crossterm.Command.PrintStyled], is what
+/// [CrosstermStyleConv#toCrossterm(jatatui.core.style.Style)] produces. The `underline_color`
+/// field is included unconditionally; upstream gates it behind the `underline-color` cargo
+/// feature, but Java has no equivalent.
 public record ContentStyle(
     Optional<tui.crossterm.Color> foregroundColor,
     Optional<tui.crossterm.Color> backgroundColor,
@@ -178,26 +178,27 @@ public final class CrosstermBackend implements Backend {
 
   @Override
   public void scrollRegionUp(int regionStart, int regionEnd, int lineCount) throws IOException {
-    scrollRegion(regionStart, regionEnd, lineCount, 'S');
+    scrollRegion(regionStart, regionEnd, lineCount, true);
   }
 
   @Override
   public void scrollRegionDown(int regionStart, int regionEnd, int lineCount) throws IOException {
-    scrollRegion(regionStart, regionEnd, lineCount, 'T');
+    scrollRegion(regionStart, regionEnd, lineCount, false);
   }
 
-  private void scrollRegion(int regionStart, int regionEnd, int lineCount, char op)
+  private void scrollRegion(int regionStart, int regionEnd, int lineCount, boolean up)
       throws IOException {
-    if (lineCount == 0) {
+    if (lineCount == 0 || regionStart >= regionEnd) {
       return;
     }
-    int top = Math.min(regionStart + 1, 0xFFFF);
-    int bottom = Math.min(regionEnd + 1, 0xFFFF);
-    enqueue(
-        List.of(
-            new Command.Print("[" + top + ";" + bottom + "r"),
-            new Command.Print("[" + lineCount + op),
-            new Command.Print("[r")));
+    int lastRow = regionEnd - 1;
+    // Scrolling by more than the height of the region blanks it, same as scrolling by the height.
+    int lines = Math.min(lineCount, regionEnd - regionStart);
+    Command command =
+        up
+            ? new Command.ScrollUpInRegion(regionStart, lastRow, lines)
+            : new Command.ScrollDownInRegion(regionStart, lastRow, lines);
+    enqueue(List.of(command));
   }
 
   // ---- internal helpers ----
@@ -3,13 +3,17 @@ package jatatui.crossterm;
 import jatatui.core.style.Color;
 import jatatui.core.style.Modifier;
 import jatatui.core.style.Style;
+import java.util.ArrayList;
+import java.util.List;
 import java.util.Optional;
 import tui.crossterm.Attribute;
+import tui.crossterm.Attributes;
 
-/// Conversion from the JNI binding's [ContentStyle] to jatatui's [Style].
+/// Conversion between the crossterm [ContentStyle] and jatatui's [Style].
 ///
 /// Mirrors the `FromCrossterm<ContentStyle> for Style` impl in upstream
-/// `ratatui-crossterm/src/lib.rs`. The `underline_color` field is included unconditionally —
+/// `ratatui-crossterm/src/lib.rs`, plus the other direction for
+/// [tui.crossterm.Command.PrintStyled]. The `underline_color` field is included unconditionally —
 /// upstream gates it behind the `underline-color` cargo feature, but Java has no equivalent.
 public final class CrosstermStyleConv {
 
@@ -47,4 +51,69 @@ public final class CrosstermStyleConv {
 
     return new Style(fg, bg, underline, addModifier, subModifier);
   }
+
+  /// Convert a jatatui [Style] to the binding's [tui.crossterm.ContentStyle], for use with
+  /// [tui.crossterm.Command.PrintStyled].
+  ///
+  /// Added modifiers become the corresponding attributes, and removed modifiers the attributes
+  /// turning them off again.
+  public static tui.crossterm.ContentStyle toCrossterm(Style value) {
+    List<Attribute> attributes = new ArrayList<>();
+    Modifier add = value.addModifier();
+    Modifier sub = value.subModifier();
+    if (sub.contains(Modifier.BOLD) || sub.contains(Modifier.DIM)) {
+      attributes.add(Attribute.NormalIntensity);
+    }
+    if (sub.contains(Modifier.ITALIC)) {
+      attributes.add(Attribute.NoItalic);
+    }
+    if (sub.contains(Modifier.UNDERLINED)) {
+      attributes.add(Attribute.NoUnderline);
+    }
+    if (sub.contains(Modifier.SLOW_BLINK) || sub.contains(Modifier.RAPID_BLINK)) {
+      attributes.add(Attribute.NoBlink);
+    }
+    if (sub.contains(Modifier.REVERSED)) {
+      attributes.add(Attribute.NoReverse);
+    }
+    if (sub.contains(Modifier.HIDDEN)) {
+      attributes.add(Attribute.NoHidden);
+    }
+    if (sub.contains(Modifier.CROSSED_OUT)) {
+      attributes.add(Attribute.NotCrossedOut);
+    }
+    if (add.contains(Modifier.BOLD)) {
+      attributes.add(Attribute.Bold);
+    }
+    if (add.contains(Modifier.DIM)) {
+      attributes.add(Attribute.Dim);
+    }
+    if (add.contains(Modifier.ITALIC)) {
+      attributes.add(Attribute.Italic);
+    }
+    if (add.contains(Modifier.UNDERLINED)) {
+      attributes.add(Attribute.Underlined);
+    }
+    if (add.contains(Modifier.SLOW_BLINK)) {
+      attributes.add(Attribute.SlowBlink);
+    }
+    if (add.contains(Modifier.RAPID_BLINK)) {
+      attributes.add(Attribute.RapidBlink);
+    }
+    if (add.contains(Modifier.REVERSED)) {
+      attributes.add(Attribute.Reverse);
+    }
+    if (add.contains(Modifier.HIDDEN)) {
+      attributes.add(Attribute.Hidden);
+    }
+    if (add.contains(Modifier.CROSSED_OUT)) {
+      attributes.add(Attribute.CrossedOut);
+    }
+
+    return new tui.crossterm.ContentStyle(
+        value.fg().map(CrosstermColorConv::toCrossterm),
+        value.bg().map(CrosstermColorConv::toCrossterm),
+        value.underlineColor().map(CrosstermColorConv::toCrossterm),
+        new Attributes(attributes));
+  }
 }
@@ -0,0 +1,60 @@
+package jatatui.crossterm;
+
+import jatatui.core.buffer.Buffer;
+import jatatui.core.buffer.Cell;
+import jatatui.core.layout.Rect;
+import jatatui.core.style.Color;
+import jatatui.core.symbols.HalfBlock;
+import jatatui.core.widgets.Widget;
+import tui.crossterm.CrosstermJni;
+import tui.crossterm.RgbaImage;
+
+/// A widget which draws an image with `▀` half blocks in true color, two pixels per cell. This
+/// works in any terminal with true color support, so it is the fallback when no graphics protocol
+/// is available.
+///
+/// The image is scaled natively to fit the area, keeping its aspect ratio, and drawn at the top
+/// left. Pixels which are mostly transparent leave the buffer alone.
+public final class HalfBlockImage implements Widget {
+
+  private final CrosstermJni jni;
+  private final RgbaImage image;
+
+  /// Creates the widget for an image decoded with [CrosstermJni#decodeImage(byte[])].
+  public HalfBlockImage(CrosstermJni jni, RgbaImage image) {
+    this.jni = jni;
+    this.image = image;
+  }
+
+  @Override
+  public void render(Rect area, Buffer buf) {
+    Rect clipped = area.intersection(buf.area());
+    if (clipped.isEmpty()) {
+      return;
+    }
+    int[] colors = jni.halfBlocks(image, clipped.width(), clipped.height());
+    for (int row = 0; row < clipped.height(); row++) {
+      for (int column = 0; column < clipped.width(); column++) {
+        int i = (row * clipped.width() + column) * 2;
+        int upper = colors[i];
+        int lower = colors[i + 1];
+        Cell cell = buf.cellAt(clipped.x() + column, clipped.y() + row);
+        if (isOpaque(upper) && isOpaque(lower)) {
+          cell.setChar(HalfBlock.UPPER).setFg(rgb(upper)).setBg(rgb(lower));
+        } else if (isOpaque(upper)) {
+          cell.setChar(HalfBlock.UPPER).setFg(rgb(upper));
+        } else if (isOpaque(lower)) {
+          cell.setChar(HalfBlock.LOWER).setFg(rgb(lower));
+        }
+      }
+    }
+  }
+
+  private static boolean isOpaque(int argb) {
+    return (argb >>> 24) >= 128;
+  }
+
+  private static Color rgb(int argb) {
+    return new Color.Rgb((argb >> 16) & 0xFF, (argb >> 8) & 0xFF, argb & 0xFF);
+  }
+}
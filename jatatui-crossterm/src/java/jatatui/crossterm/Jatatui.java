@@ -25,10 +25,16 @@ public final class Jatatui {
     return Terminal.create(new CrosstermBackend(JNI));
   }
 
-  /// Restore the terminal to a sane state: leave the alternate screen and
+  /// Restore the terminal to a sane state: undo tracked terminal state (see
+  /// [CrosstermJni#restoreTerminalState()]), leave the alternate screen and
   /// disable raw mode. Errors are logged and swallowed so this can be safely
   /// called from finally blocks and shutdown hooks.
   public static void restore() {
+    try {
+      JNI.restoreTerminalState();
+    } catch (RuntimeException e) {
+      System.err.println("Failed to restore terminal state: " + e.getMessage());
+    }
     try {
       JNI.execute(new Command.LeaveAlternateScreen());
     } catch (RuntimeException e) {
@@ -41,6 +47,19 @@ public final class Jatatui {
     }
   }
 
+  /// Recover the terminal after a crash, which may have happened in the middle of a frame:
+  /// besides what [#restore()] does, this turns off every mode the bridge can enable and resets
+  /// the scrolling region, colors and cursor style (see [CrosstermJni#recoverTerminal()]).
+  /// Errors are logged and swallowed.
+  public static void recover() {
+    try {
+      JNI.recoverTerminal();
+    } catch (RuntimeException e) {
+      System.err.println("Failed to recover terminal: " + e.getMessage());
+      restore();
+    }
+  }
+
   /// Run a function that may throw IOException with an initialized terminal,
   /// restoring the terminal afterwards regardless of whether the function
   /// returned normally or threw.
@@ -71,7 +90,7 @@ public final class Jatatui {
     Thread.UncaughtExceptionHandler previous = Thread.getDefaultUncaughtExceptionHandler();
     Thread.setDefaultUncaughtExceptionHandler(
         (t, e) -> {
-          restore();
+          recover();
           if (previous != null) {
             previous.uncaughtException(t, e);
           } else {
@@ -0,0 +1,25 @@
+package jatatui.crossterm;
+
+import jatatui.core.layout.Rect;
+import tui.crossterm.Command;
+
+/// Builds kitty graphics protocol commands from the [Rect] areas widgets are laid out in.
+///
+/// Images are transmitted once with [Command.TransmitRgbaImage] or [Command.TransmitPngImage],
+/// then placed into an area. Queue placements after the frame is drawn, because placing an image
+/// moves the cursor. Drawing text over the area later does not remove the image.
+public final class KittyGraphics {
+
+  private KittyGraphics() {}
+
+  /// Place image `imageId` so it fills `area`, drawn on top of the text.
+  public static Command.PlaceImage place(int imageId, int placementId, Rect area) {
+    return place(imageId, placementId, area, 0);
+  }
+
+  /// Place image `imageId` so it fills `area`, at the given z-index.
+  public static Command.PlaceImage place(int imageId, int placementId, Rect area, int zIndex) {
+    return new Command.PlaceImage(
+        imageId, placementId, area.x(), area.y(), area.width(), area.height(), zIndex);
+  }
+}
@@ -50,7 +50,7 @@ public final class CalendarExplorerExample {
             }
             KeyCode code = key.keyEvent().code();
             if (code instanceof KeyCode.Char ch) {
-              char c = ch.c();
+              int c = ch.codePoint();
               if (c == 'q') {
                 return;
               } else if (c == 's') {
@@ -59,7 +59,7 @@ public final class CanvasExample {
       }
       KeyCode code = key.code();
       if (code instanceof KeyCode.Char ch) {
-        switch (ch.c()) {
+        switch (ch.codePoint()) {
           case 'q' -> exit = true;
           case 'j' -> y += 1.0;
           case 'k' -> y -= 1.0;
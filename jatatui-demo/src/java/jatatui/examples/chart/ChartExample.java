@@ -104,7 +104,7 @@ public final class ChartExample {
           KeyEvent key = keyEv.keyEvent();
           if (key.kind() == KeyEventKind.Press
               && key.code() instanceof KeyCode.Char ch
-              && ch.c() == 'q') {
+              && ch.codePoint() == 'q') {
             return;
           }
         }
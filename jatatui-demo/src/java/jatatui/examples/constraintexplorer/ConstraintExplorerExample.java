@@ -144,7 +144,7 @@ public final class ConstraintExplorerExample {
       }
       KeyCode code = key.keyEvent().code();
       if (code instanceof KeyCode.Char ch) {
-        char c = ch.c();
+        int c = ch.codePoint();
         switch (c) {
           case 'q' -> exit();
           case '1' -> swapConstraint(ConstraintName.Min);
@@ -278,7 +278,7 @@ public final class ConstraintsExample {
       }
       KeyCode code = key.keyEvent().code();
       if (code instanceof KeyCode.Char ch) {
-        switch (ch.c()) {
+        switch (ch.codePoint()) {
           case 'q' -> state = AppState.Quit;
           case 'l' -> nextTab();
           case 'h' -> previousTab();
@@ -224,19 +224,19 @@ public final class CustomWidgetExample {
       return false;
     }
     KeyCode code = key.code();
-    if (code instanceof KeyCode.Char ch && ch.c() == 'q') {
+    if (code instanceof KeyCode.Char ch && ch.codePoint() == 'q') {
       return true;
     }
-    if (code instanceof KeyCode.Left || (code instanceof KeyCode.Char ch && ch.c() == 'h')) {
+    if (code instanceof KeyCode.Left || (code instanceof KeyCode.Char ch && ch.codePoint() == 'h')) {
       buttonStates[selectedButton[0]] = State.Normal;
       selectedButton[0] = Math.max(0, selectedButton[0] - 1);
       buttonStates[selectedButton[0]] = State.Selected;
     } else if (code instanceof KeyCode.Right
-        || (code instanceof KeyCode.Char ch2 && ch2.c() == 'l')) {
+        || (code instanceof KeyCode.Char ch2 && ch2.codePoint() == 'l')) {
       buttonStates[selectedButton[0]] = State.Normal;
       selectedButton[0] = Math.min(2, selectedButton[0] + 1);
       buttonStates[selectedButton[0]] = State.Selected;
-    } else if (code instanceof KeyCode.Char ch3 && ch3.c() == ' ') {
+    } else if (code instanceof KeyCode.Char ch3 && ch3.codePoint() == ' ') {
       if (buttonStates[selectedButton[0]] == State.Active) {
         buttonStates[selectedButton[0]] = State.Normal;
       } else {
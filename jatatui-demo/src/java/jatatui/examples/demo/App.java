@@ -136,7 +136,7 @@ public final class App {
     tabs.previous();
   }
 
-  public void onKey(char c) {
+  public void onKey(int c) {
     switch (c) {
       case 'q' -> this.shouldQuit = true;
       case 't' -> this.showChart = !this.showChart;
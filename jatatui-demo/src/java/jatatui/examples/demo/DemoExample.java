@@ -64,7 +64,7 @@ public final class DemoExample {
       if (event instanceof Event.Key keyEvt && keyEvt.keyEvent().kind() == KeyEventKind.Press) {
         KeyCode code = keyEvt.keyEvent().code();
         if (code instanceof KeyCode.Char ch) {
-          char c = ch.c();
+          int c = ch.codePoint();
           switch (c) {
             case 'h' -> app.onLeft();
             case 'j' -> app.onDown();
@@ -154,7 +154,7 @@ public final class App {
     }
     KeyCode code = keyEv.keyEvent().code();
     if (code instanceof KeyCode.Char ch) {
-      switch (ch.c()) {
+      switch (ch.codePoint()) {
         case 'q' -> mode = Mode.Quit;
         case 'h' -> prevTab();
         case 'l' -> nextTab();
@@ -264,7 +264,7 @@ public final class FlexExample {
       }
       KeyCode code = key.keyEvent().code();
       if (code instanceof KeyCode.Char ch) {
-        switch (ch.c()) {
+        switch (ch.codePoint()) {
           case 'q' -> state = AppState.Quit;
           case 'l' -> selectedTab = selectedTab.next();
           case 'h' -> selectedTab = selectedTab.previous();
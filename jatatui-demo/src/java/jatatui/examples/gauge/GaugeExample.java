@@ -112,9 +112,9 @@ public final class GaugeExample {
     }
     KeyCode code = key.code();
     if (code instanceof KeyCode.Char ch) {
-      if (ch.c() == ' ') {
+      if (ch.codePoint() == ' ') {
         app.start();
-      } else if (ch.c() == 'q') {
+      } else if (ch.codePoint() == 'q') {
         app.quit();
       }
     } else if (code instanceof KeyCode.Enter) {
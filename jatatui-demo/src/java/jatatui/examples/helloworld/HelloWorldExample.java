@@ -51,7 +51,7 @@ public final class HelloWorldExample {
       if (event instanceof Event.Key key
           && key.keyEvent().kind() == KeyEventKind.Press
           && key.keyEvent().code() instanceof KeyCode.Char ch
-          && ch.c() == 'q') {
+          && ch.codePoint() == 'q') {
         return true;
       }
     }
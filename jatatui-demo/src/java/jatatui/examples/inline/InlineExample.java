@@ -234,7 +234,7 @@ public final class InlineExample {
       }
 
       if (ev instanceof AppEvent.Input input) {
-        if (input.key().code() instanceof KeyCode.Char ch && ch.c() == 'q') {
+        if (input.key().code() instanceof KeyCode.Char ch && ch.codePoint() == 'q') {
           return;
         }
       } else if (ev instanceof AppEvent.Resize) {
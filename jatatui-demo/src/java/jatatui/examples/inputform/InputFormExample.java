@@ -195,10 +195,10 @@ public final class InputFormExample {
     void onKeyPress(KeyEvent event) {
       KeyCode code = event.code();
       if (code instanceof KeyCode.Char ch) {
-        value = value + ch.c();
+        value = value + Character.toString(ch.codePoint());
       } else if (code instanceof KeyCode.Backspace) {
         if (!value.isEmpty()) {
-          value = value.substring(0, value.length() - 1);
+          value = value.substring(0, value.offsetByCodePoints(value.length(), -1));
         }
       }
     }
@@ -240,7 +240,7 @@ public final class InputFormExample {
     void onKeyPress(KeyEvent event) {
       KeyCode code = event.code();
       if (code instanceof KeyCode.Char ch) {
-        char c = ch.c();
+        int c = ch.codePoint();
         if (c >= '0' && c <= '9') {
           // Mirror upstream's `saturating_mul(10).saturating_add(digit - '0')` clamped to MAX.
           long candidate = (long) value * 10L + (long) (c - '0');
@@ -50,9 +50,9 @@ public final class MouseDrawingExample {
       }
       KeyCode code = key.code();
       if (code instanceof KeyCode.Char ch) {
-        if (ch.c() == ' ') {
+        if (ch.codePoint() == ' ') {
           currentColor = new Color.Rgb(RNG.nextInt(256), RNG.nextInt(256), RNG.nextInt(256));
-        } else if (ch.c() == 'q') {
+        } else if (ch.codePoint() == 'q') {
           shouldExit = true;
         }
       } else if (code instanceof KeyCode.Esc) {
@@ -49,7 +49,7 @@ public final class PanicExample {
       if (event instanceof Event.Key keyEvt
           && keyEvt.keyEvent().kind() == KeyEventKind.Press
           && keyEvt.keyEvent().code() instanceof KeyCode.Char ch) {
-        switch (ch.c()) {
+        switch (ch.codePoint()) {
           case 'p' -> throw new RuntimeException("intentional demo panic");
           case 'e' -> throw new IOException("intentional demo error");
           case 'h' -> {
@@ -39,9 +39,9 @@ public final class PopupExample {
       if (event instanceof Event.Key keyEvt && keyEvt.keyEvent().kind() == KeyEventKind.Press) {
         KeyCode code = keyEvt.keyEvent().code();
         if (code instanceof KeyCode.Char ch) {
-          if (ch.c() == 'q') {
+          if (ch.codePoint() == 'q') {
             return;
-          } else if (ch.c() == 'p') {
+          } else if (ch.codePoint() == 'p') {
             showPopup[0] = !showPopup[0];
           }
         }
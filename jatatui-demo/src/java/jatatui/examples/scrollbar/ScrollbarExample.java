@@ -72,7 +72,7 @@ public final class ScrollbarExample {
         if (keyEvt.keyEvent().kind() != KeyEventKind.Press) continue;
         KeyCode code = keyEvt.keyEvent().code();
         if (code instanceof KeyCode.Char ch) {
-          switch (ch.c()) {
+          switch (ch.codePoint()) {
             case 'q' -> {
               return;
             }
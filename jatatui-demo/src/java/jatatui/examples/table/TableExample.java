@@ -178,7 +178,7 @@ public final class TableExample {
           return;
         }
         if (code instanceof KeyCode.Char ch) {
-          char c = ch.c();
+          int c = ch.codePoint();
           if (c == 'q') return;
           if (c == 'j') {
             nextRow();
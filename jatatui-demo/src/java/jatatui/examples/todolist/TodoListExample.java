@@ -135,7 +135,7 @@ public final class TodoListExample {
       if (code instanceof KeyCode.Esc) {
         shouldExit = true;
       } else if (code instanceof KeyCode.Char ch) {
-        switch (ch.c()) {
+        switch (ch.codePoint()) {
           case 'q' -> shouldExit = true;
           case 'h' -> selectNone();
           case 'j' -> selectNext();
@@ -70,9 +70,9 @@ public final class UserInputExample {
       characterIndex = clampCursor(moved);
     }
 
-    void enterChar(char newChar) {
+    void enterChar(int newChar) {
       int index = byteIndex();
-      input = input.substring(0, index) + newChar + input.substring(index);
+      input = input.substring(0, index) + Character.toString(newChar) + input.substring(index);
       moveCursorRight();
     }
 
@@ -148,9 +148,9 @@ public final class UserInputExample {
             }
             KeyCode code = ke.code();
             if (code instanceof KeyCode.Char ch) {
-              if (ch.c() == 'e') {
+              if (ch.codePoint() == 'e') {
                 inputMode = InputMode.Editing;
-              } else if (ch.c() == 'q') {
+              } else if (ch.codePoint() == 'q') {
                 return;
               }
             }
@@ -163,7 +163,7 @@ public final class UserInputExample {
             if (code instanceof KeyCode.Enter) {
               submitMessage();
             } else if (code instanceof KeyCode.Char ch) {
-              enterChar(ch.c());
+              enterChar(ch.codePoint());
             } else if (code instanceof KeyCode.Backspace) {
               deleteChar();
             } else if (code instanceof KeyCode.Left) {
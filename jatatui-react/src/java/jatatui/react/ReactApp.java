@@ -89,7 +89,7 @@ public final class ReactApp {
       KeyModifiers mods = keyEv.keyEvent().modifiers();
       // Ctrl-C is unconditional (OS-style abort).
       if (code instanceof KeyCode.Char ch
-          && ch.c() == 'c'
+          && ch.codePoint() == 'c'
           && (mods.bits() & KeyModifiers.CONTROL) != 0) return false;
       if (code instanceof KeyCode.Tab) {
         if ((mods.bits() & KeyModifiers.SHIFT) != 0) renderer.shiftTab();
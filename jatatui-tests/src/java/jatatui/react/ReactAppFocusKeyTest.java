@@ -77,7 +77,8 @@ class ReactAppFocusKeyTest {
   static Event keyEvent(KeyCode code, int modifierBits) {
     return new Event.Key(
         new KeyEvent(
-            code, new KeyModifiers(modifierBits), KeyEventKind.Press, new KeyEventState(0)));
+            code, new KeyModifiers(modifierBits), KeyEventKind.Press, new KeyEventState(0)),
+        0L);
   }
 
   // ---- Forward (Tab) ----
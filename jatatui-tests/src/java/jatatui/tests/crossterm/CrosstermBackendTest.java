@@ -6,17 +6,23 @@ import jatatui.core.style.Color;
 import jatatui.core.style.Modifier;
 import jatatui.core.style.Style;
 import jatatui.crossterm.ContentStyle;
+import jatatui.crossterm.CrosstermBackend;
 import jatatui.crossterm.CrosstermColorConv;
 import jatatui.crossterm.CrosstermModifierConv;
 import jatatui.crossterm.CrosstermStyleConv;
+import java.io.IOException;
+import java.util.ArrayList;
 import java.util.List;
 import java.util.Optional;
 import java.util.stream.Stream;
+import org.junit.jupiter.api.Test;
 import org.junit.jupiter.params.ParameterizedTest;
 import org.junit.jupiter.params.provider.Arguments;
 import org.junit.jupiter.params.provider.MethodSource;
 import tui.crossterm.Attribute;
 import tui.crossterm.Attributes;
+import tui.crossterm.Command;
+import tui.crossterm.CrosstermJni;
 
 /// Port of the inline `#[cfg(test)] mod tests` from upstream `ratatui-crossterm/src/lib.rs`.
 ///
@@ -165,7 +171,7 @@ public class CrosstermBackendTest {
 
   // ---- from_crossterm_content_style_underline ----
 
-  @org.junit.jupiter.api.Test
+  @Test
   public void from_crossterm_content_style_underline() {
     ContentStyle contentStyle =
         new ContentStyle(
@@ -177,4 +183,87 @@ public class CrosstermBackendTest {
         Style.DEFAULT.withUnderlineColor(Color.RED),
         CrosstermStyleConv.fromCrossterm(contentStyle));
   }
+
+  // ---- into_crossterm_content_style ----
+
+  private static tui.crossterm.ContentStyle nativeStyleWithAttrs(Attribute... attrs) {
+    return new tui.crossterm.ContentStyle(
+        Optional.empty(), Optional.empty(), Optional.empty(), new Attributes(List.of(attrs)));
+  }
+
+  static Stream<Arguments> into_crossterm_content_style_cases() {
+    return Stream.of(
+        Arguments.of(Style.DEFAULT, tui.crossterm.ContentStyle.empty()),
+        Arguments.of(
+            Style.DEFAULT
+                .withFg(Color.YELLOW)
+                .withBg(Color.LIGHT_BLUE)
+                .withUnderlineColor(Color.RED),
+            new tui.crossterm.ContentStyle(
+                Optional.of(new tui.crossterm.Color.DarkYellow()),
+                Optional.of(new tui.crossterm.Color.Blue()),
+                Optional.of(new tui.crossterm.Color.DarkRed()),
+                new Attributes(List.of()))),
+        Arguments.of(
+            Style.DEFAULT.withAddModifier(Modifier.BOLD).withAddModifier(Modifier.ITALIC),
+            nativeStyleWithAttrs(Attribute.Bold, Attribute.Italic)),
+        Arguments.of(
+            Style.DEFAULT.withRemoveModifier(Modifier.BOLD).withAddModifier(Modifier.REVERSED),
+            nativeStyleWithAttrs(Attribute.NormalIntensity, Attribute.Reverse)),
+        Arguments.of(
+            Style.DEFAULT.withRemoveModifier(Modifier.SLOW_BLINK.insert(Modifier.CROSSED_OUT)),
+            nativeStyleWithAttrs(Attribute.NoBlink, Attribute.NotCrossedOut)));
+  }
+
+  @ParameterizedTest
+  @MethodSource("into_crossterm_content_style_cases")
+  public void into_crossterm_content_style(Style style, tui.crossterm.ContentStyle contentStyle) {
+    assertEquals(contentStyle, CrosstermStyleConv.toCrossterm(style));
+  }
+
+  // ---- scroll_region ----
+
+  /// Records the enqueued commands instead of writing them to the terminal.
+  private static final class RecordingJni extends CrosstermJni {
+    final List<Command> enqueued = new ArrayList<>();
+
+    @Override
+    public void enqueue(List<Command> commands) {
+      enqueued.addAll(commands);
+    }
+  }
+
+  @Test
+  public void scroll_region_up_uses_exclusive_region_end() throws IOException {
+    RecordingJni jni = new RecordingJni();
+    new CrosstermBackend(jni).scrollRegionUp(2, 6, 1);
+    assertEquals(List.of(new Command.ScrollUpInRegion(2, 5, 1)), jni.enqueued);
+  }
+
+  @Test
+  public void scroll_region_down_uses_exclusive_region_end() throws IOException {
+    RecordingJni jni = new RecordingJni();
+    new CrosstermBackend(jni).scrollRegionDown(0, 3, 2);
+    assertEquals(List.of(new Command.ScrollDownInRegion(0, 2, 2)), jni.enqueued);
+  }
+
+  @Test
+  public void scroll_region_clamps_line_count_to_region_height() throws IOException {
+    RecordingJni jni = new RecordingJni();
+    CrosstermBackend backend = new CrosstermBackend(jni);
+    backend.scrollRegionUp(1, 4, 10);
+    backend.scrollRegionDown(1, 4, 10);
+    assertEquals(
+        List.of(new Command.ScrollUpInRegion(1, 3, 3), new Command.ScrollDownInRegion(1, 3, 3)),
+        jni.enqueued);
+  }
+
+  @Test
+  public void scroll_region_ignores_empty_scrolls() throws IOException {
+    RecordingJni jni = new RecordingJni();
+    CrosstermBackend backend = new CrosstermBackend(jni);
+    backend.scrollRegionUp(2, 6, 0);
+    backend.scrollRegionDown(3, 3, 1);
+    assertEquals(List.of(), jni.enqueued);
+  }
 }
@@ -0,0 +1,25 @@
+package jatatui.tests.crossterm;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import jatatui.core.layout.Rect;
+import jatatui.crossterm.KittyGraphics;
+import org.junit.jupiter.api.Test;
+import tui.crossterm.Command;
+
+public class KittyGraphicsTest {
+
+  @Test
+  public void place_fills_rect() {
+    assertEquals(
+        new Command.PlaceImage(7, 1, 2, 3, 10, 5, 0),
+        KittyGraphics.place(7, 1, new Rect(2, 3, 10, 5)));
+  }
+
+  @Test
+  public void place_below_text() {
+    assertEquals(
+        new Command.PlaceImage(7, 2, 0, 0, 4, 4, -1),
+        KittyGraphics.place(7, 2, new Rect(0, 0, 4, 4), -1));
+  }
+}
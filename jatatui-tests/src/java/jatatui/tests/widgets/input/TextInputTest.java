@@ -26,6 +26,13 @@ class TextInputTest {
     assertEquals(2, r.cursorPos());
   }
 
+  @Test
+  void insert_at_keeps_non_bmp_code_point_whole() {
+    TextResult r = TextInput.insertAt("ab", 1, 0x1F600);
+    assertEquals("a\uD83D\uDE00b", r.value());
+    assertEquals(3, r.cursorPos());
+  }
+
   @Test
   void insert_string() {
     TextResult r = TextInput.insertStringAt("abc", 1, "xyz");
@@ -47,6 +54,13 @@ class TextInputTest {
     assertEquals(0, r.cursorPos());
   }
 
+  @Test
+  void backspace_removes_whole_surrogate_pair() {
+    TextResult r = TextInput.backspaceAt("a\uD83D\uDE00", 3);
+    assertEquals("a", r.value());
+    assertEquals(1, r.cursorPos());
+  }
+
   @Test
   void delete_removes_char_at_cursor() {
     TextResult r = TextInput.deleteAt("hello", 1);
@@ -74,6 +88,14 @@ class TextInputTest {
     assertEquals(3, TextInput.moveRight("abc", 2).cursorPos());
   }
 
+  @Test
+  void move_steps_over_surrogate_pairs() {
+    String value = "a\uD83D\uDE00b";
+    assertEquals(3, TextInput.moveRight(value, 1).cursorPos());
+    assertEquals(1, TextInput.moveLeft(value, 3).cursorPos());
+    assertEquals("ab", TextInput.deleteAt(value, 1).value());
+  }
+
   @Test
   void move_home_end() {
     assertEquals(0, TextInput.moveHome("hello", 3).cursorPos());
@@ -141,4 +163,13 @@ class TextInputTest {
     for (int x = 0; x < 5; x++) sb.append(buf.cellAt(x, 0).symbol());
     assertEquals("6789 ", sb.toString());
   }
+
+  @Test
+  void render_non_bmp_code_point_in_one_cell() {
+    Buffer buf = Buffer.empty(new Rect(0, 0, 4, 1));
+    TextInput.of("a\uD83D\uDE00b").render(buf.area(), buf);
+    assertEquals("a", buf.cellAt(0, 0).symbol());
+    assertEquals("\uD83D\uDE00", buf.cellAt(1, 0).symbol());
+    assertEquals("b", buf.cellAt(2, 0).symbol());
+  }
 }
@@ -118,16 +118,19 @@ public final class TextInput implements Widget {
       return;
     }
 
-    // Compute scroll so cursor is in view.
-    int adjustedScroll = scrollFor(cursorPos, scrollOffset, width);
-    int visibleLen = Math.min(value.length() - adjustedScroll, width);
-    String visible = value.substring(adjustedScroll, adjustedScroll + Math.max(0, visibleLen));
+    // Compute scroll so cursor is in view. Each code point takes one cell, so the scroll offset
+    // and the cursor column count code points rather than `String` indices.
+    int cursorColumn = value.codePointCount(0, cursorPos);
+    int adjustedScroll = scrollFor(cursorColumn, scrollOffset, width);
+    int[] codePoints = value.codePoints().toArray();
+    int visibleLen = Math.min(codePoints.length - adjustedScroll, width);
+    String visible = new String(codePoints, adjustedScroll, Math.max(0, visibleLen));
 
     paintLine(buf, clipped, visible, baseStyle, width);
 
     // Draw cursor cell (only when focused).
     if (focused) {
-      int cursorScreenX = cursorPos - adjustedScroll;
+      int cursorScreenX = cursorColumn - adjustedScroll;
       if (cursorScreenX >= 0 && cursorScreenX < width) {
         int absX = clipped.left() + cursorScreenX;
         int absY = clipped.top();
@@ -141,10 +144,11 @@ public final class TextInput implements Widget {
   private static void paintLine(Buffer buf, Rect area, String text, Style style, int width) {
     int absY = area.top();
     int absX0 = area.left();
+    int[] codePoints = text.codePoints().toArray();
     for (int x = 0; x < width; x++) {
       Cell cell = buf.cellAt(absX0 + x, absY);
-      char ch = x < text.length() ? text.charAt(x) : ' ';
-      cell.setSymbol(String.valueOf(ch));
+      int ch = x < codePoints.length ? codePoints[x] : ' ';
+      cell.setSymbol(Character.toString(ch));
       cell.setStyle(style);
     }
   }
@@ -164,11 +168,14 @@ public final class TextInput implements Widget {
   /// Result of an editing operation: new value + new cursor position.
   public record TextResult(String value, int cursorPos) {}
 
-  /// Insert `ch` at the current cursor position, advancing the cursor by 1.
-  public static TextResult insertAt(String value, int cursorPos, char ch) {
+  /// Insert the code point `ch` at the current cursor position, advancing the cursor past it.
+  ///
+  /// Positions are `String` indices, so a character outside the Basic Multilingual Plane moves
+  /// the cursor by 2.
+  public static TextResult insertAt(String value, int cursorPos, int ch) {
     int p = clamp(cursorPos, 0, value.length());
-    String next = value.substring(0, p) + ch + value.substring(p);
-    return new TextResult(next, p + 1);
+    String next = value.substring(0, p) + Character.toString(ch) + value.substring(p);
+    return new TextResult(next, p + Character.charCount(ch));
   }
 
   /// Insert a string at the cursor (for paste).
@@ -178,28 +185,31 @@ public final class TextInput implements Widget {
     return new TextResult(next, p + s.length());
   }
 
-  /// Delete the character before the cursor; cursor moves left by 1. No-op at position 0.
+  /// Delete the character before the cursor; cursor moves left past it. No-op at position 0.
   public static TextResult backspaceAt(String value, int cursorPos) {
     int p = clamp(cursorPos, 0, value.length());
     if (p == 0) return new TextResult(value, 0);
-    String next = value.substring(0, p - 1) + value.substring(p);
-    return new TextResult(next, p - 1);
+    int start = previous(value, p);
+    String next = value.substring(0, start) + value.substring(p);
+    return new TextResult(next, start);
   }
 
   /// Delete the character at the cursor; cursor stays. No-op at end of string.
   public static TextResult deleteAt(String value, int cursorPos) {
     int p = clamp(cursorPos, 0, value.length());
     if (p >= value.length()) return new TextResult(value, p);
-    String next = value.substring(0, p) + value.substring(p + 1);
+    String next = value.substring(0, p) + value.substring(following(value, p));
     return new TextResult(next, p);
   }
 
   public static TextResult moveLeft(String value, int cursorPos) {
-    return new TextResult(value, Math.max(0, cursorPos - 1));
+    int p = clamp(cursorPos, 0, value.length());
+    return new TextResult(value, p == 0 ? 0 : previous(value, p));
   }
 
   public static TextResult moveRight(String value, int cursorPos) {
-    return new TextResult(value, Math.min(value.length(), cursorPos + 1));
+    int p = clamp(cursorPos, 0, value.length());
+    return new TextResult(value, p == value.length() ? p : following(value, p));
   }
 
   public static TextResult moveHome(String value, int cursorPos) {
@@ -210,6 +220,16 @@ public final class TextInput implements Widget {
     return new TextResult(value, value.length());
   }
 
+  /// The index of the code point before `p`, so surrogate pairs are never split.
+  private static int previous(String value, int p) {
+    return value.offsetByCodePoints(p, -1);
+  }
+
+  /// The index after the code point at `p`.
+  private static int following(String value, int p) {
+    return value.offsetByCodePoints(p, 1);
+  }
+
   private static int clamp(int v, int lo, int hi) {
     return Math.max(lo, Math.min(hi, v));
   }